@@ -0,0 +1,573 @@
+use std::io::{self, BufRead, Write};
+
+use serde::Serialize;
+
+use crate::input::parse_side;
+use crate::solver::Solution;
+use crate::{render, round_to, Triangle};
+
+pub const USAGE: &str = "Usage: triangle [<a> <b> <c> | --batch [<file>]] [--format text|csv|json] \
+[--precision <decimals>] [--render svg|ascii]";
+
+/// Decimal places in text output when no precision is given.
+const DEFAULT_TEXT_PRECISION: usize = 2;
+/// Most decimal places which `f64` represents meaningfully.
+const MAX_PRECISION: usize = 15;
+/// Width of ASCII drawings in characters.
+const ASCII_WIDTH: usize = 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Csv,
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Render {
+    /// SVG image instead of the report.
+    Svg,
+    /// ASCII art after the text report.
+    Ascii,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Input {
+    /// Ask the user for each side.
+    Interactive,
+    /// Sides given as arguments.
+    Sides(f64, f64, f64),
+    /// One triangle per line of the file, or of stdin if there is no file.
+    Batch(Option<String>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Options {
+    pub input: Input,
+    pub format: Format,
+    /// Number of decimal places to round to. Values are not rounded if `None`,
+    /// except in text output.
+    pub precision: Option<usize>,
+    pub render: Option<Render>,
+}
+
+impl Options {
+    /// Parses command line arguments without the program name.
+    pub fn parse(args: &[String]) -> Result<Options, String> {
+        let mut format = Format::Text;
+        let mut batch = None;
+        let mut precision = None;
+        let mut render = None;
+        let mut sides = Vec::new();
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--format" => {
+                    format = match args.next().map(String::as_str) {
+                        Some("text") => Format::Text,
+                        Some("csv") => Format::Csv,
+                        Some("json") => Format::Json,
+                        Some(other) => return Err(format!("Unknown format '{}'", other)),
+                        None => return Err("Missing format".to_string()),
+                    }
+                }
+                "--precision" => {
+                    precision = match args.next().map(|decimals| decimals.parse()) {
+                        Some(Ok(decimals)) if decimals <= MAX_PRECISION => Some(decimals),
+                        Some(Ok(_)) => return Err(format!("Precision must be at most {}", MAX_PRECISION)),
+                        Some(Err(_)) => return Err("Precision must be a non-negative integer".to_string()),
+                        None => return Err("Missing precision".to_string()),
+                    }
+                }
+                "--render" => {
+                    render = match args.next().map(String::as_str) {
+                        Some("svg") => Some(Render::Svg),
+                        Some("ascii") => Some(Render::Ascii),
+                        Some(other) => return Err(format!("Unknown rendering '{}'", other)),
+                        None => return Err("Missing rendering".to_string()),
+                    }
+                }
+                "--batch" => batch = Some(None),
+                _ if batch == Some(None) && sides.is_empty() && !arg.starts_with("--") => {
+                    batch = Some(Some(arg.clone()))
+                }
+                _ => sides.push(parse_side(arg).map_err(|error| format!("'{}': {}", arg, error))?),
+            }
+        }
+
+        let input = match (batch, sides.as_slice()) {
+            (Some(file), []) => Input::Batch(file),
+            (None, []) => Input::Interactive,
+            (None, &[a, b, c]) => Input::Sides(a, b, c),
+            (Some(_), _) => return Err("Sides cannot be combined with --batch".to_string()),
+            (None, _) => return Err("Exactly three sides are required".to_string()),
+        };
+        match (render, &input) {
+            (Some(_), _) if format != Format::Text => {
+                return Err("Rendering is only supported with text format".to_string())
+            }
+            (Some(Render::Svg), Input::Batch(_)) => {
+                return Err("SVG can only be rendered for a single triangle".to_string())
+            }
+            _ => {}
+        }
+        Ok(Options { input, format, precision, render })
+    }
+}
+
+/// Parses three sides separated by whitespace or commas.
+pub fn parse_sides(line: &str) -> Result<(f64, f64, f64), String> {
+    let sides = line
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|side| !side.is_empty())
+        .map(|side| parse_side(side).map_err(|error| format!("'{}': {}", side, error)))
+        .collect::<Result<Vec<_>, _>>()?;
+    match sides.as_slice() {
+        &[a, b, c] => Ok((a, b, c)),
+        _ => Err(format!("Expected three sides, found {}", sides.len())),
+    }
+}
+
+/// # Report
+/// All computed properties of a triangle. Angles are in degrees.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Report {
+    pub a: f64,
+    pub b: f64,
+    pub c: f64,
+    pub circumference: Option<f64>,
+    pub semiperimeter: f64,
+    pub area: f64,
+    pub alpha: f64,
+    pub beta: f64,
+    pub gamma: f64,
+    pub angle_kind: String,
+    pub is_right: bool,
+    pub is_isosceles: bool,
+    pub is_equilateral: bool,
+    pub inradius: f64,
+    pub circumradius: f64,
+    pub height_a: f64,
+    pub height_b: f64,
+    pub height_c: f64,
+    pub median_a: f64,
+    pub median_b: f64,
+    pub median_c: f64,
+}
+
+const CSV_HEADER: &str = "a,b,c,circumference,semiperimeter,area,alpha,beta,gamma,angle_kind,\
+is_right,is_isosceles,is_equilateral,inradius,circumradius,\
+height_a,height_b,height_c,median_a,median_b,median_c";
+
+impl Report {
+    pub fn of(triangle: Triangle<f64>) -> Report {
+        let solution = Solution::of(triangle);
+        let (a, b, c) = triangle.sides();
+        let (alpha, beta, gamma) = solution.angles;
+        let (height_a, height_b, height_c) = solution.heights;
+        let (median_a, median_b, median_c) = solution.medians;
+        Report {
+            a,
+            b,
+            c,
+            circumference: triangle.circumference(),
+            semiperimeter: solution.semiperimeter,
+            area: solution.area,
+            alpha: alpha.to_degrees(),
+            beta: beta.to_degrees(),
+            gamma: gamma.to_degrees(),
+            angle_kind: triangle.angle_kind().to_string(),
+            is_right: triangle.is_right(),
+            is_isosceles: triangle.is_isosceles(),
+            is_equilateral: triangle.is_equilateral(),
+            inradius: solution.inradius,
+            circumradius: solution.circumradius,
+            height_a,
+            height_b,
+            height_c,
+            median_a,
+            median_b,
+            median_c,
+        }
+    }
+
+    /// Copy of the report with every number rounded to `decimals` places.
+    pub fn rounded(&self, decimals: usize) -> Report {
+        let round = |value: f64| round_to(value, decimals as i32);
+        Report {
+            a: round(self.a),
+            b: round(self.b),
+            c: round(self.c),
+            circumference: self.circumference.map(round),
+            semiperimeter: round(self.semiperimeter),
+            area: round(self.area),
+            alpha: round(self.alpha),
+            beta: round(self.beta),
+            gamma: round(self.gamma),
+            angle_kind: self.angle_kind.clone(),
+            inradius: round(self.inradius),
+            circumradius: round(self.circumradius),
+            height_a: round(self.height_a),
+            height_b: round(self.height_b),
+            height_c: round(self.height_c),
+            median_a: round(self.median_a),
+            median_b: round(self.median_b),
+            median_c: round(self.median_c),
+            ..*self
+        }
+    }
+
+    fn write_text<W: Write>(&self, output: &mut W, precision: usize) -> io::Result<()> {
+        let is = |value: bool| if value { "" } else { "not " };
+        writeln!(output, "Sides are {}, {} and {}.", self.a, self.b, self.c)?;
+        match self.circumference {
+            Some(circumference) => writeln!(output, "Circumference is {}.", circumference)?,
+            None => writeln!(output, "Circumference is too large.")?,
+        }
+        writeln!(output, "Semiperimeter is {:.*}.", precision, self.semiperimeter)?;
+        writeln!(output, "Area is {:.*}.", precision, self.area)?;
+        writeln!(
+            output,
+            "Angles are {:.*}°, {:.*}° and {:.*}°.",
+            precision, self.alpha, precision, self.beta, precision, self.gamma
+        )?;
+        writeln!(output, "Triangle is {}-angled.", self.angle_kind)?;
+        writeln!(output, "Triangle is {}right.", is(self.is_right))?;
+        writeln!(output, "Triangle is {}isosceles.", is(self.is_isosceles))?;
+        writeln!(output, "Triangle is {}equilateral.", is(self.is_equilateral))?;
+        writeln!(output, "Inradius is {:.*}.", precision, self.inradius)?;
+        writeln!(output, "Circumradius is {:.*}.", precision, self.circumradius)?;
+        writeln!(
+            output,
+            "Heights are {:.*}, {:.*} and {:.*}.",
+            precision, self.height_a, precision, self.height_b, precision, self.height_c
+        )?;
+        writeln!(
+            output,
+            "Medians are {:.*}, {:.*} and {:.*}.",
+            precision, self.median_a, precision, self.median_b, precision, self.median_c
+        )
+    }
+
+    fn write_csv<W: Write>(&self, output: &mut W) -> io::Result<()> {
+        let circumference = self.circumference.map(|c| c.to_string()).unwrap_or_default();
+        writeln!(
+            output,
+            "{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{}",
+            self.a, self.b, self.c, circumference, self.semiperimeter, self.area,
+            self.alpha, self.beta, self.gamma, self.angle_kind,
+            self.is_right, self.is_isosceles, self.is_equilateral, self.inradius, self.circumradius,
+            self.height_a, self.height_b, self.height_c, self.median_a, self.median_b, self.median_c
+        )
+    }
+
+    fn write_json<W: Write>(&self, output: &mut W) -> io::Result<()> {
+        serde_json::to_writer(&mut *output, self)?;
+        writeln!(output)
+    }
+}
+
+/// # ReportWriter
+/// Writes reports of triangles one after another in the given format.
+/// JSON is written as one object per line.
+pub struct ReportWriter<W: Write> {
+    output: W,
+    format: Format,
+    precision: Option<usize>,
+    render: Option<Render>,
+    written: usize,
+}
+
+impl<W: Write> ReportWriter<W> {
+    pub fn create(output: W, format: Format, precision: Option<usize>) -> ReportWriter<W> {
+        ReportWriter {
+            output,
+            format,
+            precision,
+            render: None,
+            written: 0,
+        }
+    }
+
+    /// Draws every triangle, see [`Render`](enum.Render.html).
+    pub fn with_render(self, render: Option<Render>) -> ReportWriter<W> {
+        ReportWriter { render, ..self }
+    }
+
+    pub fn write(&mut self, triangle: &Triangle<f64>) -> io::Result<()> {
+        if self.render == Some(Render::Svg) {
+            self.written += 1;
+            return self.output.write_all(render::svg(triangle).as_bytes());
+        }
+        self.write_report(&Report::of(*triangle))?;
+        if self.render == Some(Render::Ascii) {
+            writeln!(self.output)?;
+            self.output.write_all(render::ascii(triangle, ASCII_WIDTH).as_bytes())?;
+        }
+        Ok(())
+    }
+
+    fn write_report(&mut self, report: &Report) -> io::Result<()> {
+        let rounded;
+        let report = match self.precision {
+            Some(decimals) if self.format != Format::Text => {
+                rounded = report.rounded(decimals);
+                &rounded
+            }
+            _ => report,
+        };
+        match self.format {
+            Format::Text => {
+                if self.written > 0 {
+                    writeln!(self.output)?;
+                }
+                let precision = self.precision.unwrap_or(DEFAULT_TEXT_PRECISION);
+                report.write_text(&mut self.output, precision)?
+            }
+            Format::Csv => {
+                if self.written == 0 {
+                    writeln!(self.output, "{}", CSV_HEADER)?;
+                }
+                report.write_csv(&mut self.output)?
+            }
+            Format::Json => report.write_json(&mut self.output)?,
+        }
+        self.written += 1;
+        Ok(())
+    }
+}
+
+/// Reports every triangle in the input, one per line. Empty lines and lines starting with `#` are skipped.
+/// Lines which are not a valid triangle are reported to `errors`.
+///
+/// Returns the number of invalid lines.
+pub fn run_batch<R: BufRead, W: Write, E: Write>(
+    input: R,
+    writer: &mut ReportWriter<W>,
+    mut errors: E,
+) -> io::Result<usize> {
+    let mut invalid = 0;
+    for (index, line) in input.lines().enumerate() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let triangle = parse_sides(line)
+            .and_then(|(a, b, c)| Triangle::new(a, b, c).map_err(|error| error.to_string()));
+        match triangle {
+            Ok(triangle) => writer.write(&triangle)?,
+            Err(error) => {
+                invalid += 1;
+                writeln!(errors, "Line {}: {}", index + 1, error)?;
+            }
+        }
+    }
+    Ok(invalid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    fn run(input: &str, format: Format) -> (String, String, usize) {
+        run_with_precision(input, format, None)
+    }
+
+    fn run_with_precision(input: &str, format: Format, precision: Option<usize>) -> (String, String, usize) {
+        run_with_render(input, format, precision, None)
+    }
+
+    fn run_with_render(
+        input: &str,
+        format: Format,
+        precision: Option<usize>,
+        render: Option<Render>,
+    ) -> (String, String, usize) {
+        let (mut output, mut errors) = (Vec::new(), Vec::new());
+        let mut writer = ReportWriter::create(&mut output, format, precision).with_render(render);
+        let invalid = run_batch(input.as_bytes(), &mut writer, &mut errors).unwrap();
+        (String::from_utf8(output).unwrap(), String::from_utf8(errors).unwrap(), invalid)
+    }
+
+    #[test]
+    fn no_arguments_mean_interactive_input() {
+        let options = Options::parse(&[]).unwrap();
+        assert_eq!(
+            options,
+            Options { input: Input::Interactive, format: Format::Text, precision: None, render: None }
+        );
+    }
+
+    #[test]
+    fn parses_sides_and_format() {
+        let options = Options::parse(&args(&["3", "4", "5.5", "--format", "json"])).unwrap();
+        assert_eq!(
+            options,
+            Options {
+                input: Input::Sides(3.0, 4.0, 5.5),
+                format: Format::Json,
+                precision: None,
+                render: None,
+            }
+        );
+    }
+
+    #[test]
+    fn parses_batch_with_and_without_file() {
+        let options = Options::parse(&args(&["--format", "csv", "--batch"])).unwrap();
+        assert_eq!(options.input, Input::Batch(None));
+        let options = Options::parse(&args(&["--batch", "sides.txt"])).unwrap();
+        assert_eq!(options.input, Input::Batch(Some("sides.txt".to_string())));
+    }
+
+    #[test]
+    fn rejects_invalid_arguments() {
+        assert!(Options::parse(&args(&["3", "4"])).is_err());
+        assert!(Options::parse(&args(&["3", "4", "x"])).is_err());
+        assert!(Options::parse(&args(&["--format", "xml"])).is_err());
+        assert!(Options::parse(&args(&["--format"])).is_err());
+        assert!(Options::parse(&args(&["--batch", "file", "3"])).is_err());
+        assert!(Options::parse(&args(&["--precision", "-1"])).is_err());
+        assert!(Options::parse(&args(&["--precision"])).is_err());
+        assert!(Options::parse(&args(&["--render", "png"])).is_err());
+    }
+
+    #[test]
+    fn parse_sides_accepts_commas_and_whitespace() {
+        assert_eq!(parse_sides("3, 4,5"), Ok((3.0, 4.0, 5.0)));
+        assert_eq!(parse_sides("  3\t4 5  "), Ok((3.0, 4.0, 5.0)));
+        assert!(parse_sides("3 4").is_err());
+        assert_eq!(parse_sides("3 4 five"), Err("'five': Side must be a number".to_string()));
+        assert_eq!(parse_sides("3 -4 5"), Err("'-4': Side cannot be negative".to_string()));
+        assert_eq!(parse_sides("3 4 1e400"), Err("'1e400': Side is too large".to_string()));
+    }
+
+    #[test]
+    fn report_contains_all_properties() {
+        let report = Report::of(Triangle::new(3.0, 4.0, 5.0).unwrap());
+        assert_eq!(report.circumference, Some(12.0));
+        assert_eq!(report.area, 6.0);
+        assert!((report.gamma - 90.0).abs() < 1e-9);
+        assert_eq!(report.angle_kind, "right");
+        assert!(report.is_right);
+        assert!(!report.is_isosceles);
+        assert_eq!(report.inradius, 1.0);
+        assert_eq!(report.circumradius, 2.5);
+    }
+
+    #[test]
+    fn batch_writes_csv_with_one_header() {
+        let (output, errors, invalid) = run("3 4 5\n\n# comment\n2 2 2\n", Format::Csv);
+        let lines: Vec<_> = output.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], CSV_HEADER);
+        assert!(lines[1].starts_with("3,4,5,12,6,6,"));
+        assert!(lines[2].contains(",acute,false,true,true,"));
+        assert_eq!(errors, "");
+        assert_eq!(invalid, 0);
+    }
+
+    #[test]
+    fn batch_writes_one_json_object_per_line() {
+        let (output, _, _) = run("3 4 5\n2 2 2\n", Format::Json);
+        let reports: Vec<serde_json::Value> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(reports.len(), 2);
+        assert_eq!(reports[0]["area"], 6.0);
+        assert_eq!(reports[1]["is_equilateral"], true);
+    }
+
+    #[test]
+    fn batch_reports_invalid_lines_and_continues() {
+        let (output, errors, invalid) = run("1 2 3\n3 4 5\nnot a triangle\n", Format::Text);
+        assert!(output.starts_with("Sides are 3, 4 and 5.\n"));
+        assert_eq!(
+            errors,
+            "Line 1: This is not a valid triangle: a + b must be greater than c\n\
+             Line 3: 'not': Side must be a number\n"
+        );
+        assert_eq!(invalid, 2);
+    }
+
+    #[test]
+    fn text_reports_are_separated_by_empty_line() {
+        let (output, _, _) = run("3 4 5\n3 4 5\n", Format::Text);
+        assert_eq!(output.matches("\n\nSides are").count(), 1);
+    }
+
+    #[test]
+    fn parses_precision() {
+        let options = Options::parse(&args(&["--precision", "4", "3", "4", "5"])).unwrap();
+        assert_eq!(options.precision, Some(4));
+        let options = Options::parse(&args(&["--precision", "15"])).unwrap();
+        assert_eq!(options.precision, Some(15));
+        assert_eq!(
+            Options::parse(&args(&["--precision", "16"])),
+            Err("Precision must be at most 15".to_string())
+        );
+        assert!(Options::parse(&args(&["--precision", "4294967296"])).is_err());
+    }
+
+    #[test]
+    fn json_is_not_rounded_without_precision() {
+        let (output, _, _) = run("1 1 1\n", Format::Json);
+        let report: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(report["area"], 3f64.sqrt() / 4.0);
+    }
+
+    #[test]
+    fn json_is_rounded_to_precision() {
+        let (output, _, _) = run_with_precision("1 1 1\n", Format::Json, Some(3));
+        let report: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(report["area"], 0.433);
+        assert_eq!(report["alpha"], 60.0);
+    }
+
+    #[test]
+    fn text_uses_precision() {
+        let (output, _, _) = run_with_precision("1 1 1\n", Format::Text, Some(4));
+        assert!(output.contains("Area is 0.4330.\n"));
+        let (output, _, _) = run("1 1 1\n", Format::Text);
+        assert!(output.contains("Area is 0.43.\n"));
+    }
+
+    #[test]
+    fn parses_render() {
+        let options = Options::parse(&args(&["3", "4", "5", "--render", "svg"])).unwrap();
+        assert_eq!(options.render, Some(Render::Svg));
+        let options = Options::parse(&args(&["--batch", "--render", "ascii"])).unwrap();
+        assert_eq!(options.render, Some(Render::Ascii));
+    }
+
+    #[test]
+    fn render_requires_text_format() {
+        assert!(Options::parse(&args(&["3", "4", "5", "--render", "ascii", "--format", "csv"])).is_err());
+    }
+
+    #[test]
+    fn svg_cannot_be_rendered_in_batch() {
+        assert!(Options::parse(&args(&["--batch", "--render", "svg"])).is_err());
+    }
+
+    #[test]
+    fn svg_replaces_report() {
+        let (output, _, _) = run_with_render("3 4 5\n", Format::Text, None, Some(Render::Svg));
+        assert!(output.starts_with("<svg"));
+        assert!(!output.contains("Area is"));
+    }
+
+    #[test]
+    fn ascii_follows_report() {
+        let (output, _, _) = run_with_render("3 4 5\n", Format::Text, None, Some(Render::Ascii));
+        assert!(output.starts_with("Sides are 3, 4 and 5."));
+        assert!(output.ends_with("Right angle at C.\n"));
+    }
+}
@@ -0,0 +1,399 @@
+use std::ops::{Add, Mul, Sub};
+
+use crate::side::Side;
+use crate::{Triangle, TriangleError};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Point2 {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Point2 {
+    pub fn new(x: f64, y: f64) -> Point2 {
+        Point2 { x, y }
+    }
+
+    pub fn distance(self, other: Point2) -> f64 {
+        (self - other).length()
+    }
+
+    fn length(self) -> f64 {
+        self.x.hypot(self.y)
+    }
+
+    /// Z coordinate of the cross product of two vectors.
+    fn cross(self, other: Point2) -> f64 {
+        self.x * other.y - self.y * other.x
+    }
+}
+
+impl Add for Point2 {
+    type Output = Point2;
+
+    fn add(self, other: Point2) -> Point2 {
+        Point2::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for Point2 {
+    type Output = Point2;
+
+    fn sub(self, other: Point2) -> Point2 {
+        Point2::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Mul<f64> for Point2 {
+    type Output = Point2;
+
+    fn mul(self, factor: f64) -> Point2 {
+        Point2::new(self.x * factor, self.y * factor)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Point3 {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Point3 {
+    pub fn new(x: f64, y: f64, z: f64) -> Point3 {
+        Point3 { x, y, z }
+    }
+
+    pub fn distance(self, other: Point3) -> f64 {
+        (self - other).length()
+    }
+
+    fn length(self) -> f64 {
+        (self.x * self.x + self.y * self.y + self.z * self.z).sqrt()
+    }
+
+    fn cross(self, other: Point3) -> Point3 {
+        Point3::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+}
+
+impl Add for Point3 {
+    type Output = Point3;
+
+    fn add(self, other: Point3) -> Point3 {
+        Point3::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl Sub for Point3 {
+    type Output = Point3;
+
+    fn sub(self, other: Point3) -> Point3 {
+        Point3::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl Mul<f64> for Point3 {
+    type Output = Point3;
+
+    fn mul(self, factor: f64) -> Point3 {
+        Point3::new(self.x * factor, self.y * factor, self.z * factor)
+    }
+}
+
+/// Tells whether the cross product of two edges is negligible compared to their lengths.
+fn is_collinear(cross_length: f64, first_edge: f64, second_edge: f64) -> bool {
+    cross_length <= f64::RELATIVE_EPSILON * first_edge * second_edge
+}
+
+/// Order in which the vertices are listed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    CounterClockwise,
+    Clockwise,
+}
+
+/// Vertices of the triangle laid out like by [`Triangle2::from_sides`](struct.Triangle2.html#method.from_sides),
+/// which also works for needles whose sides do not form a triangle in `f64`.
+pub(crate) fn lay_out<T: Side>(triangle: &Triangle<T>) -> (Point2, Point2, Point2) {
+    let (_, b, c) = triangle.sides_f64();
+    let (alpha, _, _) = triangle.angles();
+    (Point2::new(0.0, 0.0), Point2::new(c, 0.0), Point2::new(b * alpha.cos(), b * alpha.sin()))
+}
+
+/// # Triangle2
+/// Triangle given by vertices `a`, `b` and `c` in a plane.
+/// Side `a` of its [`Triangle`](../struct.Triangle.html) is opposite to vertex `a`, and so on.
+///
+/// # Examples
+/// ```
+/// use triangle::geometry::{Point2, Triangle2};
+///
+/// let triangle = Triangle2::new(Point2::new(0.0, 0.0), Point2::new(4.0, 0.0), Point2::new(0.0, 3.0)).unwrap();
+/// assert_eq!(triangle.signed_area(), 6.0);
+/// assert!(triangle.triangle().is_right());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Triangle2 {
+    a: Point2,
+    b: Point2,
+    c: Point2,
+    triangle: Triangle<f64>,
+}
+
+impl Triangle2 {
+    /// Creates a triangle, unless the vertices lie on one line.
+    pub fn new(a: Point2, b: Point2, c: Point2) -> Result<Triangle2, TriangleError> {
+        let (ab, ac) = (b - a, c - a);
+        if is_collinear(ab.cross(ac).abs(), ab.length(), ac.length()) {
+            return Err(TriangleError::Collinear);
+        }
+        let triangle = Triangle::new(b.distance(c), a.distance(c), a.distance(b))?;
+        Ok(Triangle2 { a, b, c, triangle })
+    }
+
+    /// Places vertex `a` at the origin and vertex `b` on the positive x axis,
+    /// with vertex `c` above it. Fails when the sides of a needle no longer form
+    /// a triangle after rounding them to `f64`.
+    pub fn from_sides<T: Side>(triangle: &Triangle<T>) -> Result<Triangle2, TriangleError> {
+        let (a, b, c) = triangle.sides_f64();
+        let (vertex_a, vertex_b, vertex_c) = lay_out(triangle);
+        Ok(Triangle2 {
+            a: vertex_a,
+            b: vertex_b,
+            c: vertex_c,
+            triangle: Triangle::new(a, b, c)?,
+        })
+    }
+
+    pub fn vertices(&self) -> (Point2, Point2, Point2) {
+        (self.a, self.b, self.c)
+    }
+
+    /// Triangle given by side lengths.
+    pub fn triangle(&self) -> Triangle<f64> {
+        self.triangle
+    }
+
+    /// Area by the shoelace formula, positive for counter-clockwise vertices.
+    pub fn signed_area(&self) -> f64 {
+        (self.a.cross(self.b) + self.b.cross(self.c) + self.c.cross(self.a)) / 2.0
+    }
+
+    pub fn area(&self) -> f64 {
+        self.signed_area().abs()
+    }
+
+    pub fn orientation(&self) -> Orientation {
+        if self.signed_area() > 0.0 {
+            Orientation::CounterClockwise
+        } else {
+            Orientation::Clockwise
+        }
+    }
+
+    /// Intersection of the medians.
+    pub fn centroid(&self) -> Point2 {
+        (self.a + self.b + self.c) * (1.0 / 3.0)
+    }
+
+    /// Center of the inscribed circle.
+    pub fn incenter(&self) -> Point2 {
+        let (a, b, c) = self.triangle.sides_f64();
+        (self.a * a + self.b * b + self.c * c) * (1.0 / (a + b + c))
+    }
+
+    /// Center of the circumscribed circle.
+    pub fn circumcenter(&self) -> Point2 {
+        let (b, c) = (self.b - self.a, self.c - self.a);
+        let d = 2.0 * b.cross(c);
+        let (b_squared, c_squared) = (b.x * b.x + b.y * b.y, c.x * c.x + c.y * c.y);
+        self.a
+            + Point2::new(
+                (c.y * b_squared - b.y * c_squared) / d,
+                (b.x * c_squared - c.x * b_squared) / d,
+            )
+    }
+
+    /// Intersection of the altitudes.
+    pub fn orthocenter(&self) -> Point2 {
+        // Euler line: orthocenter = 3 * centroid - 2 * circumcenter
+        self.a + self.b + self.c - self.circumcenter() * 2.0
+    }
+
+    /// Tells whether the point lies inside the triangle or on its edge.
+    pub fn contains(&self, point: Point2) -> bool {
+        let edges = [(self.a, self.b), (self.b, self.c), (self.c, self.a)];
+        let sign = self.signed_area().signum();
+        edges
+            .iter()
+            .all(|&(start, end)| (end - start).cross(point - start) * sign >= 0.0)
+    }
+}
+
+/// # Triangle3
+/// Triangle given by vertices `a`, `b` and `c` in space.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Triangle3 {
+    a: Point3,
+    b: Point3,
+    c: Point3,
+    triangle: Triangle<f64>,
+}
+
+impl Triangle3 {
+    /// Creates a triangle, unless the vertices lie on one line.
+    pub fn new(a: Point3, b: Point3, c: Point3) -> Result<Triangle3, TriangleError> {
+        let (ab, ac) = (b - a, c - a);
+        if is_collinear(ab.cross(ac).length(), ab.length(), ac.length()) {
+            return Err(TriangleError::Collinear);
+        }
+        let triangle = Triangle::new(b.distance(c), a.distance(c), a.distance(b))?;
+        Ok(Triangle3 { a, b, c, triangle })
+    }
+
+    pub fn vertices(&self) -> (Point3, Point3, Point3) {
+        (self.a, self.b, self.c)
+    }
+
+    /// Triangle given by side lengths.
+    pub fn triangle(&self) -> Triangle<f64> {
+        self.triangle
+    }
+
+    pub fn area(&self) -> f64 {
+        (self.b - self.a).cross(self.c - self.a).length() / 2.0
+    }
+
+    /// Unit vector perpendicular to the triangle, by the right-hand rule.
+    pub fn normal(&self) -> Point3 {
+        let cross = (self.b - self.a).cross(self.c - self.a);
+        cross * (1.0 / cross.length())
+    }
+
+    /// Intersection of the medians.
+    pub fn centroid(&self) -> Point3 {
+        (self.a + self.b + self.c) * (1.0 / 3.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: Point2, expected: Point2) {
+        assert!(actual.distance(expected) < 1e-9, "{:?} is not {:?}", actual, expected);
+    }
+
+    fn right_triangle() -> Triangle2 {
+        Triangle2::new(Point2::new(0.0, 0.0), Point2::new(4.0, 0.0), Point2::new(0.0, 3.0)).unwrap()
+    }
+
+    #[test]
+    fn collinear_points_are_not_a_triangle() {
+        let result = Triangle2::new(Point2::new(0.0, 0.0), Point2::new(1.0, 1.0), Point2::new(3.0, 3.0));
+        assert_eq!(result, Err(TriangleError::Collinear));
+    }
+
+    #[test]
+    fn sides_are_opposite_to_vertices() {
+        assert_eq!(right_triangle().triangle().sides(), (5.0, 3.0, 4.0));
+    }
+
+    #[test]
+    fn signed_area_depends_on_orientation() {
+        let triangle = right_triangle();
+        assert_eq!(triangle.signed_area(), 6.0);
+        assert_eq!(triangle.orientation(), Orientation::CounterClockwise);
+
+        let (a, b, c) = triangle.vertices();
+        let reversed = Triangle2::new(a, c, b).unwrap();
+        assert_eq!(reversed.signed_area(), -6.0);
+        assert_eq!(reversed.area(), 6.0);
+        assert_eq!(reversed.orientation(), Orientation::Clockwise);
+    }
+
+    #[test]
+    fn area_matches_side_based_area() {
+        let triangle = Triangle2::new(Point2::new(1.0, 2.0), Point2::new(7.0, -1.0), Point2::new(3.0, 5.0)).unwrap();
+        assert!((triangle.area() - triangle.triangle().area()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn centers_of_right_triangle() {
+        let triangle = right_triangle();
+        assert_close(triangle.centroid(), Point2::new(4.0 / 3.0, 1.0));
+        assert_close(triangle.incenter(), Point2::new(1.0, 1.0));
+        assert_close(triangle.circumcenter(), Point2::new(2.0, 1.5));
+        assert_close(triangle.orthocenter(), Point2::new(0.0, 0.0));
+    }
+
+    #[test]
+    fn orthocenter_of_acute_triangle() {
+        let triangle = Triangle2::new(Point2::new(0.0, 0.0), Point2::new(4.0, 0.0), Point2::new(1.0, 3.0)).unwrap();
+        assert_close(triangle.orthocenter(), Point2::new(1.0, 1.0));
+    }
+
+    #[test]
+    fn contains_points_inside_and_on_edges() {
+        let triangle = right_triangle();
+        assert!(triangle.contains(Point2::new(1.0, 1.0)));
+        assert!(triangle.contains(Point2::new(2.0, 0.0)));
+        assert!(triangle.contains(Point2::new(0.0, 3.0)));
+        assert!(!triangle.contains(Point2::new(3.0, 3.0)));
+        assert!(!triangle.contains(Point2::new(-0.1, 1.0)));
+    }
+
+    #[test]
+    fn contains_does_not_depend_on_orientation() {
+        let (a, b, c) = right_triangle().vertices();
+        let reversed = Triangle2::new(a, c, b).unwrap();
+        assert!(reversed.contains(Point2::new(1.0, 1.0)));
+        assert!(!reversed.contains(Point2::new(3.0, 3.0)));
+    }
+
+    #[test]
+    fn from_sides_lays_out_vertices() {
+        let triangle = Triangle2::from_sides(&Triangle::new(5, 3, 4).unwrap()).unwrap();
+        let (a, b, c) = triangle.vertices();
+        assert_close(a, Point2::new(0.0, 0.0));
+        assert_close(b, Point2::new(4.0, 0.0));
+        assert_close(c, Point2::new(0.0, 3.0));
+        assert!((triangle.area() - 6.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn from_sides_of_huge_needle() {
+        let needle = Triangle::new(1u64 << 60, 1 << 60, 1).unwrap();
+        assert!(matches!(Triangle2::from_sides(&needle), Err(TriangleError::InequalityViolated { .. })));
+        assert_eq!(lay_out(&needle).1, Point2::new(1.0, 0.0));
+    }
+
+    #[test]
+    fn triangle3_area_and_normal() {
+        let triangle = Triangle3::new(
+            Point3::new(0.0, 0.0, 1.0),
+            Point3::new(4.0, 0.0, 1.0),
+            Point3::new(0.0, 3.0, 1.0),
+        )
+        .unwrap();
+        assert_eq!(triangle.area(), 6.0);
+        assert_eq!(triangle.normal(), Point3::new(0.0, 0.0, 1.0));
+        assert_eq!(triangle.centroid(), Point3::new(4.0 / 3.0, 1.0, 1.0));
+        assert!(triangle.triangle().is_right());
+    }
+
+    #[test]
+    fn triangle3_rejects_collinear_points() {
+        let result = Triangle3::new(
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(1.0, 2.0, 3.0),
+            Point3::new(2.0, 4.0, 6.0),
+        );
+        assert_eq!(result, Err(TriangleError::Collinear));
+    }
+}
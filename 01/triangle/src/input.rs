@@ -0,0 +1,245 @@
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::str::FromStr;
+
+use crate::Side;
+
+/// Reason why the entered text is not a valid side.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidSide {
+    NotANumber,
+    NotWhole,
+    Negative,
+    Zero,
+    /// Number is too large for the type of the side.
+    Overflow,
+}
+
+impl fmt::Display for InvalidSide {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InvalidSide::NotANumber => write!(f, "Side must be a number"),
+            InvalidSide::NotWhole => write!(f, "Side must be a whole number"),
+            InvalidSide::Negative => write!(f, "Side cannot be negative"),
+            InvalidSide::Zero => write!(f, "Side cannot be zero"),
+            InvalidSide::Overflow => write!(f, "Side is too large"),
+        }
+    }
+}
+
+/// Parses a positive side, or tells why the text is not one.
+pub fn parse_side<T: Side + FromStr>(text: &str) -> Result<T, InvalidSide> {
+    let text = text.trim();
+    match text.parse::<T>() {
+        Ok(side) => match side.partial_cmp(&T::zero()) {
+            None => Err(InvalidSide::NotANumber),
+            Some(_) if !side.is_finite() => Err(InvalidSide::Overflow),
+            Some(ordering) if ordering.is_lt() => Err(InvalidSide::Negative),
+            Some(ordering) if ordering.is_eq() => Err(InvalidSide::Zero),
+            Some(_) => Ok(side),
+        },
+        // text is not valid for `T`, but it can still be a number
+        Err(_) => match text.parse::<f64>() {
+            Err(_) => Err(InvalidSide::NotANumber),
+            Ok(value) if value.is_nan() => Err(InvalidSide::NotANumber),
+            Ok(value) if value < 0.0 => Err(InvalidSide::Negative),
+            Ok(0.0) => Err(InvalidSide::Zero),
+            Ok(_) if text.trim_start_matches('+').bytes().all(|byte| byte.is_ascii_digit()) => {
+                Err(InvalidSide::Overflow)
+            }
+            Ok(_) => Err(InvalidSide::NotWhole),
+        },
+    }
+}
+
+#[derive(Debug)]
+pub enum PromptError {
+    Io(io::Error),
+    /// Input ended before a valid answer was entered.
+    EndOfInput,
+    /// No valid answer within the maximum number of attempts.
+    TooManyAttempts(usize),
+}
+
+impl fmt::Display for PromptError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PromptError::Io(error) => write!(f, "{}", error),
+            PromptError::EndOfInput => write!(f, "Input ended before a valid answer was entered"),
+            PromptError::TooManyAttempts(attempts) => write!(f, "No valid answer in {} attempts", attempts),
+        }
+    }
+}
+
+impl Error for PromptError {}
+
+impl From<io::Error> for PromptError {
+    fn from(error: io::Error) -> Self {
+        PromptError::Io(error)
+    }
+}
+
+/// # Prompt
+/// Asks questions and reads answers until a valid one is entered,
+/// telling the user why each invalid answer was rejected.
+///
+/// # Examples
+/// ```
+/// use triangle::input::Prompt;
+///
+/// let mut output = Vec::new();
+/// let mut prompt = Prompt::create("-3\n3\n".as_bytes(), &mut output);
+/// assert_eq!(prompt.read_side::<u32>("a").unwrap(), 3);
+/// assert_eq!(
+///     String::from_utf8(output).unwrap(),
+///     "Please enter side 'a'\nSide cannot be negative\nPlease enter side 'a'\nSide 'a' is: 3\n"
+/// );
+/// ```
+pub struct Prompt<R: BufRead, W: Write> {
+    input: R,
+    output: W,
+    max_attempts: Option<usize>,
+}
+
+impl<R: BufRead, W: Write> Prompt<R, W> {
+    /// Creates a prompt which asks until a valid answer is entered.
+    pub fn create(input: R, output: W) -> Prompt<R, W> {
+        Prompt {
+            input,
+            output,
+            max_attempts: None,
+        }
+    }
+
+    /// Gives up after the given number of invalid answers.
+    pub fn with_max_attempts(self, max_attempts: usize) -> Prompt<R, W> {
+        Prompt {
+            max_attempts: Some(max_attempts),
+            ..self
+        }
+    }
+
+    /// Asks the question until `parse` accepts the answer.
+    /// Errors returned by `parse` are shown to the user.
+    pub fn ask<T, E, F>(&mut self, question: &str, parse: F) -> Result<T, PromptError>
+    where
+        E: fmt::Display,
+        F: Fn(&str) -> Result<T, E>,
+    {
+        let mut attempts = 0;
+        loop {
+            if self.max_attempts == Some(attempts) {
+                return Err(PromptError::TooManyAttempts(attempts));
+            }
+            attempts += 1;
+
+            writeln!(self.output, "{}", question)?;
+            let mut answer = String::new();
+            if self.input.read_line(&mut answer)? == 0 {
+                return Err(PromptError::EndOfInput);
+            }
+            match parse(answer.trim()) {
+                Ok(value) => return Ok(value),
+                Err(error) => writeln!(self.output, "{}", error)?,
+            }
+        }
+    }
+
+    pub fn read_side<T: Side + FromStr>(&mut self, name: &str) -> Result<T, PromptError> {
+        let side = self.ask(&format!("Please enter side '{}'", name), parse_side)?;
+        writeln!(self.output, "Side '{}' is: {}", name, side)?;
+        Ok(side)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_side<T: Side + FromStr>(input: &str, max_attempts: usize) -> (Result<T, PromptError>, String) {
+        let mut output = Vec::new();
+        let result = Prompt::create(input.as_bytes(), &mut output)
+            .with_max_attempts(max_attempts)
+            .read_side("a");
+        (result, String::from_utf8(output).unwrap())
+    }
+
+    #[test]
+    fn parse_side_accepts_positive_numbers() {
+        assert_eq!(parse_side::<u32>(" 42 "), Ok(42));
+        assert_eq!(parse_side::<f64>("2.5"), Ok(2.5));
+        assert_eq!(parse_side::<i8>("+7"), Ok(7));
+    }
+
+    #[test]
+    fn parse_side_rejects_negative_numbers() {
+        assert_eq!(parse_side::<u32>("-5"), Err(InvalidSide::Negative));
+        assert_eq!(parse_side::<i32>("-5"), Err(InvalidSide::Negative));
+        assert_eq!(parse_side::<f64>("-0.5"), Err(InvalidSide::Negative));
+    }
+
+    #[test]
+    fn parse_side_rejects_zero() {
+        assert_eq!(parse_side::<u32>("0"), Err(InvalidSide::Zero));
+        assert_eq!(parse_side::<u32>("-0"), Err(InvalidSide::Zero));
+        assert_eq!(parse_side::<f64>("0.0"), Err(InvalidSide::Zero));
+    }
+
+    #[test]
+    fn parse_side_rejects_text() {
+        assert_eq!(parse_side::<u32>("five"), Err(InvalidSide::NotANumber));
+        assert_eq!(parse_side::<u32>(""), Err(InvalidSide::NotANumber));
+        assert_eq!(parse_side::<f64>("NaN"), Err(InvalidSide::NotANumber));
+    }
+
+    #[test]
+    fn parse_side_rejects_fractions_for_integers() {
+        assert_eq!(parse_side::<u32>("2.5"), Err(InvalidSide::NotWhole));
+    }
+
+    #[test]
+    fn parse_side_rejects_too_large_numbers() {
+        assert_eq!(parse_side::<u8>("256"), Err(InvalidSide::Overflow));
+        assert_eq!(parse_side::<u32>("99999999999999999999"), Err(InvalidSide::Overflow));
+        assert_eq!(parse_side::<f64>("1e400"), Err(InvalidSide::Overflow));
+    }
+
+    #[test]
+    fn read_side_reports_rejected_answers() {
+        let (result, output) = read_side::<u32>("abc\n0\n12\n", 5);
+        assert_eq!(result.unwrap(), 12);
+        assert_eq!(
+            output,
+            "Please enter side 'a'\nSide must be a number\n\
+             Please enter side 'a'\nSide cannot be zero\n\
+             Please enter side 'a'\nSide 'a' is: 12\n"
+        );
+    }
+
+    #[test]
+    fn read_side_gives_up_after_max_attempts() {
+        let (result, output) = read_side::<u32>("x\ny\n3\n", 2);
+        assert!(matches!(result, Err(PromptError::TooManyAttempts(2))));
+        assert_eq!(output.matches("Please enter side 'a'").count(), 2);
+    }
+
+    #[test]
+    fn read_side_fails_at_end_of_input() {
+        let (result, _) = read_side::<u32>("x\n", 5);
+        assert!(matches!(result, Err(PromptError::EndOfInput)));
+    }
+
+    #[test]
+    fn ask_accepts_custom_parser() {
+        let mut output = Vec::new();
+        let mut prompt = Prompt::create("maybe\nyes\n".as_bytes(), &mut output);
+        let answer = prompt.ask("Continue?", |answer| match answer {
+            "yes" => Ok(true),
+            "no" => Ok(false),
+            _ => Err("Answer yes or no"),
+        });
+        assert!(answer.unwrap());
+        assert_eq!(String::from_utf8(output).unwrap(), "Continue?\nAnswer yes or no\nContinue?\n");
+    }
+}
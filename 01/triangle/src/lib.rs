@@ -1,137 +1,659 @@
-use std::cmp::{max, min};
-use std::io;
-
-pub fn get_side_from_user(name: &str) -> u32 {
-    loop {
-        println!("Please enter side '{}'", name);
-        let mut side = String::new();
-
-        io::stdin().read_line(&mut side).expect("Stdin not working");
-        let side = match side.trim().parse() {
-            Ok(0) => {
-                println!("Side of a rectangle cannot be zero");
-                continue;
-            }
-            Ok(val) => val,
-            Err(_) => continue
-        };
-        println!("Side '{}' is: {}", name, side);
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt;
+
+use rational::Rational;
+pub use side::{IntegerSide, Side};
+
+pub mod cli;
+pub mod geometry;
+pub mod input;
+pub mod mesh;
+pub mod rational;
+pub mod render;
+pub mod side;
+pub mod solver;
+pub mod triples;
+
+/// Name of a side of a triangle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SideName {
+    A,
+    B,
+    C,
+}
 
-        break side;
+impl fmt::Display for SideName {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SideName::A => write!(f, "a"),
+            SideName::B => write!(f, "b"),
+            SideName::C => write!(f, "c"),
+        }
     }
 }
 
-pub fn verify_triangle(a: u32, b: u32, c: u32) {
-    if a + b <= c || a + c <= b || b + c <= a {
-        panic!("This is not a valid triangle!");
+/// Reason why three sides do not form a triangle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriangleError {
+    /// Side is not a finite number.
+    NotFinite(SideName),
+    /// Side is zero or negative.
+    NotPositive(SideName),
+    /// Sum of sides `first` and `second` is not greater than side `opposite`.
+    InequalityViolated {
+        first: SideName,
+        second: SideName,
+        opposite: SideName,
+    },
+    /// Vertices lie on one line.
+    Collinear,
+}
+
+impl fmt::Display for TriangleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TriangleError::NotFinite(side) => {
+                write!(f, "This is not a valid triangle: {} must be a finite number", side)
+            }
+            TriangleError::NotPositive(side) => {
+                write!(f, "This is not a valid triangle: {} must be positive", side)
+            }
+            TriangleError::InequalityViolated { first, second, opposite } => write!(
+                f,
+                "This is not a valid triangle: {} + {} must be greater than {}",
+                first, second, opposite
+            ),
+            TriangleError::Collinear => write!(f, "This is not a valid triangle: vertices lie on one line"),
+        }
     }
 }
 
-fn get_semiperimeter(a: u32, b: u32, c: u32) -> f32 {
-    (a + b + c) as f32 / 2.0
+impl Error for TriangleError {}
+
+/// Rounds `value` to the given number of decimal places.
+pub fn round_to(value: f64, decimals: i32) -> f64 {
+    let factor = 10f64.powi(decimals);
+    (value * factor).round() / factor
 }
 
-pub fn triangle_circumference(a: u32, b: u32, c: u32) -> u32 {
-    a + b + c
+/// Classification of a triangle by its largest angle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AngleKind {
+    Acute,
+    Right,
+    Obtuse,
 }
 
-pub fn triangle_area(a: u32, b: u32, c: u32) -> f32 {
-    let s = get_semiperimeter(a, b, c);
-    (((s * (s - a as f32) * (s - b as f32) * (s - c as f32)) as f32).sqrt() * 100.0).round() / 100.0
+impl fmt::Display for AngleKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AngleKind::Acute => write!(f, "acute"),
+            AngleKind::Right => write!(f, "right"),
+            AngleKind::Obtuse => write!(f, "obtuse"),
+        }
+    }
 }
 
-pub fn is_equilateral(a: u32, b: u32, c: u32) -> bool {
-    a == b && b == c
+/// # Triangle
+/// Triangle with sides `a`, `b` and `c` which always satisfy the triangle inequality.
+/// Sides can be of any [`Side`](side/trait.Side.html) type, e.g. `u32` or `f64`.
+///
+/// # Examples
+/// ```
+/// use triangle::Triangle;
+///
+/// let triangle = Triangle::new(3, 4, 5).unwrap();
+/// assert_eq!(triangle.circumference(), Some(12));
+/// assert!(Triangle::new(1, 2, 3).is_err());
+/// assert!(Triangle::new(2.5, 6.0, 6.5).unwrap().is_right());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Triangle<T: Side> {
+    a: T,
+    b: T,
+    c: T,
 }
 
-pub fn is_isosceles(a: u32, b: u32, c: u32) -> bool {
-    a == b || a == c || b == c
+impl<T: IntegerSide> Triangle<T> {
+    /// Exact square of the area, or `None` if the computation does not fit into `u128`.
+    ///
+    /// # Examples
+    /// ```
+    /// use triangle::rational::Rational;
+    /// use triangle::Triangle;
+    ///
+    /// let triangle = Triangle::new(3u32, 4, 5).unwrap();
+    /// assert_eq!(triangle.area_squared_exact(), Some(Rational::new(36, 1)));
+    /// ```
+    pub fn area_squared_exact(&self) -> Option<Rational> {
+        let (a, b, c) = (self.a.to_u128(), self.b.to_u128(), self.c.to_u128());
+        // 16 * area² = (a + b + c)(-a + b + c)(a - b + c)(a + b - c)
+        let product = a
+            .checked_add(b)?
+            .checked_add(c)?
+            .checked_mul(b + c - a)?
+            .checked_mul(a + c - b)?
+            .checked_mul(a + b - c)?;
+        Some(Rational::new(product, 16))
+    }
 }
 
-pub fn is_right(a: u32, b: u32, c: u32) -> bool {
-    let smallest = min(a, min(b, c));
-    let median = max(min(a, b), min(max(a, b), c));
-    let largest = max(a, max(b, c));
-    (smallest ^ 2) + (median ^ 2) == largest ^ 2
+impl<T: Side> Triangle<T> {
+    /// Creates a triangle, or tells which side or triangle inequality is invalid.
+    pub fn new(a: T, b: T, c: T) -> Result<Triangle<T>, TriangleError> {
+        use SideName::*;
+        for &(side, name) in [(a, A), (b, B), (c, C)].iter() {
+            if !side.is_finite() {
+                return Err(TriangleError::NotFinite(name));
+            }
+            if side <= T::zero() {
+                return Err(TriangleError::NotPositive(name));
+            }
+        }
+        let inequalities = [(a, b, c, A, B, C), (a, c, b, A, C, B), (b, c, a, B, C, A)];
+        for &(x, y, z, first, second, opposite) in inequalities.iter() {
+            // sum which overflows is certainly greater than the third side
+            if x.checked_add(y).is_some_and(|sum| sum <= z) {
+                return Err(TriangleError::InequalityViolated { first, second, opposite });
+            }
+        }
+        Ok(Triangle { a, b, c })
+    }
+
+    pub fn sides(&self) -> (T, T, T) {
+        (self.a, self.b, self.c)
+    }
+
+    /// Tells whether the triangles have the same sides, in any order.
+    pub fn is_congruent(&self, other: &Triangle<T>) -> bool {
+        let (sides, other_sides) = (self.sorted_sides(), other.sorted_sides());
+        sides.iter().zip(other_sides.iter()).all(|(x, y)| x.approx_eq(*y))
+    }
+
+    /// Tells whether the triangles have proportional sides, i.e. the same shape.
+    pub fn is_similar(&self, other: &Triangle<T>) -> bool {
+        self.canonical().is_congruent(&other.canonical())
+    }
+
+    /// Representative of all triangles similar to this one, with sides ordered from the smallest.
+    /// Integer sides are divided by their greatest common divisor,
+    /// floating-point sides are scaled so that the largest is 1.
+    ///
+    /// Canonical forms of triangles with integer sides are equal exactly when the triangles are similar,
+    /// so they can be used to deduplicate or hash triangles by shape.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::collections::HashSet;
+    /// use triangle::Triangle;
+    ///
+    /// let triangles = [(3, 4, 5), (5, 4, 3), (6, 8, 10), (5, 5, 5)];
+    /// let shapes: HashSet<_> = triangles
+    ///     .iter()
+    ///     .map(|&(a, b, c)| Triangle::new(a, b, c).unwrap().canonical())
+    ///     .collect();
+    /// assert_eq!(shapes.len(), 2);
+    /// ```
+    pub fn canonical(&self) -> Triangle<T> {
+        let [a, b, c] = T::normalize(self.sorted_sides());
+        Triangle { a, b, c }
+    }
+
+    /// Key for hashing triangles with floating-point sides by shape.
+    /// Sides of the canonical form are rounded to the given number of decimal places.
+    pub fn shape_key(&self, decimals: i32) -> [i64; 3] {
+        let factor = 10f64.powi(decimals);
+        let (a, b, c) = self.canonical().sides_f64();
+        [a, b, c].map(|side| (side * factor).round() as i64)
+    }
+
+    /// Sides ordered from the smallest to the largest.
+    fn sorted_sides(&self) -> [T; 3] {
+        let mut sides = [self.a, self.b, self.c];
+        // sides are never NaN, so they are totally ordered
+        sides.sort_by(|x, y| x.partial_cmp(y).unwrap());
+        sides
+    }
+
+    fn semiperimeter(&self) -> f64 {
+        (self.a.to_f64() + self.b.to_f64() + self.c.to_f64()) / 2.0
+    }
+
+    /// Returns `None` if the circumference does not fit into `T`.
+    pub fn circumference(&self) -> Option<T> {
+        self.a.checked_add(self.b)?.checked_add(self.c)
+    }
+
+    /// Sides converted to `f64`.
+    fn sides_f64(&self) -> (f64, f64, f64) {
+        (self.a.to_f64(), self.b.to_f64(), self.c.to_f64())
+    }
+
+    /// Area by Kahan's numerically stable variant of Heron's formula.
+    /// Unlike the plain formula, it stays accurate for needle-like triangles.
+    ///
+    /// Use [`round_to`](fn.round_to.html) to round the result.
+    pub fn area(&self) -> f64 {
+        let [c, b, a] = self.sorted_sides();
+        let (a, b, c) = (a.to_f64(), b.to_f64(), c.to_f64());
+        // the parentheses are essential for the stability
+        let product = (a + (b + c)) * (c - (a - b)) * (c + (a - b)) * (a + (b - c));
+        product.max(0.0).sqrt() / 4.0
+    }
+
+    /// Angles in radians opposite to sides `a`, `b` and `c`, by the law of cosines.
+    pub fn angles(&self) -> (f64, f64, f64) {
+        let (a, b, c) = self.sides_f64();
+        let angle = |opposite: f64, x: f64, y: f64| {
+            ((x * x + y * y - opposite * opposite) / (2.0 * x * y)).clamp(-1.0, 1.0).acos()
+        };
+        (angle(a, b, c), angle(b, a, c), angle(c, a, b))
+    }
+
+    /// Radius of the inscribed circle.
+    pub fn inradius(&self) -> f64 {
+        self.area() / self.semiperimeter()
+    }
+
+    /// Radius of the circumscribed circle.
+    pub fn circumradius(&self) -> f64 {
+        let (a, b, c) = self.sides_f64();
+        a * b * c / (4.0 * self.area())
+    }
+
+    /// Heights perpendicular to sides `a`, `b` and `c`.
+    pub fn heights(&self) -> (f64, f64, f64) {
+        let (a, b, c) = self.sides_f64();
+        let double_area = 2.0 * self.area();
+        (double_area / a, double_area / b, double_area / c)
+    }
+
+    /// Medians to sides `a`, `b` and `c`.
+    pub fn medians(&self) -> (f64, f64, f64) {
+        let (a, b, c) = self.sides_f64();
+        let median = |side: f64, x: f64, y: f64| (2.0 * x * x + 2.0 * y * y - side * side).sqrt() / 2.0;
+        (median(a, b, c), median(b, a, c), median(c, a, b))
+    }
+
+    pub fn is_equilateral(&self) -> bool {
+        self.a.approx_eq(self.b) && self.b.approx_eq(self.c)
+    }
+
+    pub fn is_isosceles(&self) -> bool {
+        self.a.approx_eq(self.b) || self.a.approx_eq(self.c) || self.b.approx_eq(self.c)
+    }
+
+    /// Compares squares of the sides exactly for integers,
+    /// and with tolerance for floating-point numbers.
+    pub fn angle_kind(&self) -> AngleKind {
+        let [smallest, median, largest] = self.sorted_sides();
+        match T::cmp_sum_of_squares(smallest, median, largest) {
+            Ordering::Greater => AngleKind::Acute,
+            Ordering::Equal => AngleKind::Right,
+            Ordering::Less => AngleKind::Obtuse,
+        }
+    }
+
+    pub fn is_right(&self) -> bool {
+        self.angle_kind() == AngleKind::Right
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
 
     #[test]
-    #[should_panic]
-    fn verify_triangle_panics_when_sum_of_two_sides_is_equal_to_the_third() {
+    fn new_fails_when_sum_of_two_sides_is_equal_to_the_third() {
         let a = 10;
         let b = 20;
         let c = a + b;
-        verify_triangle(a, b, c);
+        assert_eq!(
+            Triangle::new(a, b, c),
+            Err(TriangleError::InequalityViolated {
+                first: SideName::A,
+                second: SideName::B,
+                opposite: SideName::C,
+            })
+        );
     }
 
     #[test]
-    #[should_panic]
-    fn verify_triangle_panics_when_sum_of_two_sides_is_smaller_than_the_third() {
+    fn new_fails_when_sum_of_two_sides_is_smaller_than_the_third() {
         let a = 10;
         let b = 20;
         let c = a + b + 1;
-        verify_triangle(a, b, c);
+        assert!(Triangle::new(a, b, c).is_err());
+    }
+
+    #[test]
+    fn new_reports_which_inequality_failed() {
+        assert_eq!(
+            Triangle::new(30, 10, 10),
+            Err(TriangleError::InequalityViolated {
+                first: SideName::B,
+                second: SideName::C,
+                opposite: SideName::A,
+            })
+        );
     }
 
     #[test]
-    fn verify_triangle_completes_normally_when_sum_of_two_sides_is_larger_than_the_third() {
+    fn new_succeeds_when_sum_of_two_sides_is_larger_than_the_third() {
         let a = 10;
         let b = 20;
         let c = a + b - 1;
-        verify_triangle(a, b, c);
+        assert!(Triangle::new(a, b, c).is_ok());
+    }
+
+    #[test]
+    fn error_message_names_the_failed_inequality() {
+        let error = Triangle::new(1, 5, 2).unwrap_err();
+        assert_eq!(error.to_string(), "This is not a valid triangle: a + c must be greater than b");
+    }
+
+    #[test]
+    fn circumference_returns_circumference() {
+        assert_eq!(Triangle::new(10, 15, 20).unwrap().circumference(), Some(45));
+    }
+
+    #[test]
+    fn circumference_does_not_overflow() {
+        let triangle = Triangle::new(u32::MAX, u32::MAX, u32::MAX).unwrap();
+        assert_eq!(triangle.circumference(), None);
+    }
+
+    #[test]
+    fn new_does_not_overflow_for_large_sides() {
+        assert!(Triangle::new(u32::MAX, u32::MAX, 1).is_ok());
+        assert!(Triangle::new(u8::MAX, 1, u8::MAX).is_ok());
+    }
+
+    #[test]
+    fn new_rejects_non_positive_sides() {
+        assert_eq!(Triangle::new(0, 1, 1), Err(TriangleError::NotPositive(SideName::A)));
+        assert_eq!(Triangle::new(3, -4, 5), Err(TriangleError::NotPositive(SideName::B)));
+        assert_eq!(Triangle::new(3.0, 4.0, -0.5), Err(TriangleError::NotPositive(SideName::C)));
+    }
+
+    #[test]
+    fn new_rejects_non_finite_sides() {
+        assert_eq!(Triangle::new(f64::NAN, 1.0, 1.0), Err(TriangleError::NotFinite(SideName::A)));
+        assert_eq!(Triangle::new(1.0, f32::INFINITY, 1.0), Err(TriangleError::NotFinite(SideName::B)));
+    }
+
+    #[test]
+    fn float_sides_are_supported() {
+        let triangle = Triangle::new(2.5, 2.5, 2.5).unwrap();
+        assert_eq!(triangle.circumference(), Some(7.5));
+        assert!(triangle.is_equilateral());
+    }
+
+    #[test]
+    fn float_isosceles_tolerates_rounding() {
+        assert!(Triangle::new(0.1 + 0.2, 0.3, 0.5).unwrap().is_isosceles());
     }
 
     #[test]
-    fn triangle_circumference_returns_circumference() {
-        assert_eq!(triangle_circumference(10, 15, 20), 45);
+    fn float_right_triangle_tolerates_rounding() {
+        assert!(Triangle::new(0.3, 0.4, 0.5).unwrap().is_right());
+        assert!(Triangle::new(1.0, 1.0, 2f64.sqrt()).unwrap().is_right());
+        assert!(!Triangle::new(1.0, 1.0, 1.414).unwrap().is_right());
     }
 
     #[test]
-    fn triangle_area_returns_area() {
-        assert_eq!(triangle_area(10, 15, 20), 72.62);
+    fn area_returns_area() {
+        assert_eq!(round_to(Triangle::new(10, 15, 20).unwrap().area(), 2), 72.62);
     }
 
     #[test]
-    fn get_semiperimetr_returns_semiperimeter() {
-        assert_eq!(get_semiperimeter(10, 15, 20), 22.5);
+    fn area_is_not_rounded() {
+        assert_eq!(Triangle::new(1, 1, 1).unwrap().area(), 3f64.sqrt() / 4.0);
+    }
+
+    #[test]
+    fn area_of_needle_like_triangle_is_accurate() {
+        // plain Heron's formula gives 9.999999809638329
+        let triangle = Triangle::new(100000.0, 99999.99979, 0.00029).unwrap();
+        assert_eq!(triangle.area(), 10.000000077021038);
+    }
+
+    #[test]
+    fn area_of_huge_triangle_is_accurate() {
+        let side = 1u64 << 60;
+        let area = Triangle::new(side, side, side).unwrap().area();
+        let expected = 3f64.sqrt() / 4.0 * (side as f64).powi(2);
+        assert!((area - expected).abs() / expected < 1e-15);
+    }
+
+    #[test]
+    fn area_squared_exact_is_a_fraction() {
+        let triangle = Triangle::new(10u32, 15, 20).unwrap();
+        // 16 * area² = 45 * 25 * 15 * 5
+        assert_eq!(triangle.area_squared_exact(), Some(Rational::new(84375, 16)));
+    }
+
+    #[test]
+    fn area_squared_exact_of_equilateral_triangle() {
+        assert_eq!(Triangle::new(2u8, 2, 2).unwrap().area_squared_exact(), Some(Rational::new(3, 1)));
+    }
+
+    #[test]
+    fn area_squared_exact_does_not_overflow() {
+        let triangle = Triangle::new(u64::MAX, u64::MAX, u64::MAX).unwrap();
+        assert_eq!(triangle.area_squared_exact(), None);
+    }
+
+    #[test]
+    fn area_matches_area_squared_exact() {
+        let triangle = Triangle::new(13u32, 14, 15).unwrap();
+        assert_eq!(triangle.area_squared_exact(), Some(Rational::new(84 * 84, 1)));
+        assert_eq!(triangle.area(), 84.0);
+    }
+
+    #[test]
+    fn round_to_rounds_to_decimals() {
+        assert_eq!(round_to(72.6184, 2), 72.62);
+        assert_eq!(round_to(72.6184, 0), 73.0);
+        assert_eq!(round_to(1234.5, -2), 1200.0);
+    }
+
+    #[test]
+    fn semiperimetr_returns_semiperimeter() {
+        assert_eq!(Triangle::new(10, 15, 20).unwrap().semiperimeter(), 22.5);
     }
 
     #[test]
     fn equilateral() {
-        assert!(is_equilateral(5, 5, 5));
+        assert!(Triangle::new(5, 5, 5).unwrap().is_equilateral());
     }
 
     #[test]
     fn not_equilateral() {
-        assert!(!is_equilateral(5, 5, 4));
+        assert!(!Triangle::new(5, 5, 4).unwrap().is_equilateral());
     }
 
     #[test]
     fn isosceles() {
-        assert!(is_isosceles(4, 5, 5));
+        assert!(Triangle::new(4, 5, 5).unwrap().is_isosceles());
     }
 
     #[test]
     fn equilateral_is_isosceles() {
-        assert!(is_isosceles(5, 5, 5));
+        assert!(Triangle::new(5, 5, 5).unwrap().is_isosceles());
     }
 
     #[test]
     fn not_isosceles() {
-        assert!(!is_isosceles(5, 7, 4));
+        assert!(!Triangle::new(5, 7, 4).unwrap().is_isosceles());
     }
 
     #[test]
     fn right_triangle() {
-        assert!(is_right(3, 4, 5));
+        assert!(Triangle::new(3, 4, 5).unwrap().is_right());
     }
 
     #[test]
     fn not_right_triangle() {
-        assert!(!is_right(3, 4, 6));
+        assert!(!Triangle::new(3, 4, 6).unwrap().is_right());
+    }
+
+    #[test]
+    fn right_triangle_with_sides_in_any_order() {
+        assert!(Triangle::new(5, 3, 4).unwrap().is_right());
+        assert!(Triangle::new(4, 5, 3).unwrap().is_right());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn xor_is_not_mistaken_for_square() {
+        // 2 ^ 2 + 3 ^ 2 == 0 + 1 == 1 == 3 ^ 2 when `^` is XOR
+        assert!(!Triangle::new(2, 3, 3).unwrap().is_right());
+    }
+
+    #[test]
+    fn acute_triangle() {
+        assert_eq!(Triangle::new(5, 5, 5).unwrap().angle_kind(), AngleKind::Acute);
+        assert_eq!(Triangle::new(4, 5, 6).unwrap().angle_kind(), AngleKind::Acute);
+    }
+
+    #[test]
+    fn obtuse_triangle() {
+        assert_eq!(Triangle::new(3, 4, 6).unwrap().angle_kind(), AngleKind::Obtuse);
+        assert_eq!(Triangle::new(2.0, 2.0, 3.5).unwrap().angle_kind(), AngleKind::Obtuse);
+    }
+
+    #[test]
+    fn angle_kind_of_huge_integer_triangle() {
+        let triangle = Triangle::new(u64::MAX - 1, u64::MAX, u64::MAX).unwrap();
+        assert_eq!(triangle.angle_kind(), AngleKind::Acute);
+    }
+
+    fn pythagorean_triple() -> impl Strategy<Value = (u64, u64, u64)> {
+        pythagorean_triple_below(1 << 20, 1 << 20)
+    }
+
+    /// Pythagorean triple `(k(m² - n²), 2kmn, k(m² + n²))` from Euclid's formula.
+    fn pythagorean_triple_below(max_m: u64, max_k: u64) -> impl Strategy<Value = (u64, u64, u64)> {
+        (2..max_m)
+            .prop_flat_map(move |m| (Just(m), 1..m, 1..max_k))
+            .prop_map(|(m, n, k)| (k * (m * m - n * n), k * 2 * m * n, k * (m * m + n * n)))
+    }
+
+    proptest! {
+        #[test]
+        fn pythagorean_triples_are_right((a, b, c) in pythagorean_triple()) {
+            prop_assert_eq!(Triangle::new(a, b, c).unwrap().angle_kind(), AngleKind::Right);
+            prop_assert_eq!(Triangle::new(c, a, b).unwrap().angle_kind(), AngleKind::Right);
+        }
+
+        #[test]
+        fn longer_hypotenuse_is_obtuse((a, b, c) in pythagorean_triple()) {
+            prop_assert_eq!(Triangle::new(a, b, c + 1).unwrap().angle_kind(), AngleKind::Obtuse);
+        }
+
+        #[test]
+        fn shorter_hypotenuse_is_acute((a, b, c) in pythagorean_triple()) {
+            prop_assume!(a.max(b) < c - 1);
+            prop_assert_eq!(Triangle::new(a, b, c - 1).unwrap().angle_kind(), AngleKind::Acute);
+        }
+
+        #[test]
+        fn scaled_float_pythagorean_triples_are_right(
+            (a, b, c) in pythagorean_triple(),
+            scale in 1e-3f64..1e3,
+        ) {
+            let (a, b, c) = (a as f64 * scale, b as f64 * scale, c as f64 * scale);
+            prop_assert!(Triangle::new(a, b, c).unwrap().is_right());
+        }
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-9, "{} is not {}", actual, expected);
+    }
+
+    #[test]
+    fn angles_of_right_triangle() {
+        let (alpha, beta, gamma) = Triangle::new(3, 4, 5).unwrap().angles();
+        assert_close(alpha, (3.0f64 / 5.0).asin());
+        assert_close(beta, (4.0f64 / 5.0).asin());
+        assert_close(gamma, std::f64::consts::FRAC_PI_2);
+    }
+
+    #[test]
+    fn radii_of_right_triangle() {
+        let triangle = Triangle::new(3, 4, 5).unwrap();
+        assert_close(triangle.inradius(), 1.0);
+        assert_close(triangle.circumradius(), 2.5);
+    }
+
+    #[test]
+    fn heights_of_right_triangle() {
+        let (h_a, h_b, h_c) = Triangle::new(3, 4, 5).unwrap().heights();
+        assert_close(h_a, 4.0);
+        assert_close(h_b, 3.0);
+        assert_close(h_c, 2.4);
+    }
+
+    #[test]
+    fn medians_of_equilateral_triangle() {
+        let (m_a, m_b, m_c) = Triangle::new(2, 2, 2).unwrap().medians();
+        let expected = 3f64.sqrt();
+        assert_close(m_a, expected);
+        assert_close(m_b, expected);
+        assert_close(m_c, expected);
+    }
+
+    #[test]
+    fn congruent_triangles_have_same_sides_in_any_order() {
+        let triangle = Triangle::new(3, 4, 5).unwrap();
+        assert!(triangle.is_congruent(&Triangle::new(5, 3, 4).unwrap()));
+        assert!(!triangle.is_congruent(&Triangle::new(6, 8, 10).unwrap()));
+    }
+
+    #[test]
+    fn congruence_of_floats_tolerates_rounding() {
+        let triangle = Triangle::new(0.1 + 0.2, 0.4, 0.5).unwrap();
+        assert!(triangle.is_congruent(&Triangle::new(0.5, 0.4, 0.3).unwrap()));
+    }
+
+    #[test]
+    fn similar_triangles_have_proportional_sides() {
+        let triangle = Triangle::new(3, 4, 5).unwrap();
+        assert!(triangle.is_similar(&Triangle::new(10, 6, 8).unwrap()));
+        assert!(!triangle.is_similar(&Triangle::new(3, 4, 6).unwrap()));
+        assert!(Triangle::new(1.5, 2.0, 2.5).unwrap().is_similar(&Triangle::new(0.3, 0.5, 0.4).unwrap()));
+    }
+
+    #[test]
+    fn canonical_form_of_integer_triangle() {
+        assert_eq!(Triangle::new(10, 6, 8).unwrap().canonical(), Triangle::new(3, 4, 5).unwrap());
+        assert_eq!(Triangle::new(7, 7, 7).unwrap().canonical(), Triangle::new(1, 1, 1).unwrap());
+    }
+
+    #[test]
+    fn canonical_form_of_float_triangle() {
+        let canonical = Triangle::new(5.0, 3.0, 4.0).unwrap().canonical();
+        assert_eq!(canonical.sides(), (0.6, 0.8, 1.0));
+    }
+
+    #[test]
+    fn shape_key_of_similar_float_triangles_is_equal() {
+        let triangle = Triangle::new(0.3, 0.4, 0.5).unwrap();
+        let scaled = Triangle::new(3.0 * 1.1, 4.0 * 1.1, 5.0 * 1.1).unwrap();
+        assert_eq!(triangle.shape_key(6), scaled.shape_key(6));
+        assert_eq!(triangle.shape_key(2), [60, 80, 100]);
+    }
+
+    proptest! {
+        #[test]
+        fn scaled_triangles_are_similar((a, b, c) in pythagorean_triple_below(1 << 10, 1 << 10), scale in 2u64..1000) {
+            let triangle = Triangle::new(a, b, c).unwrap();
+            let scaled = Triangle::new(a * scale, c * scale, b * scale).unwrap();
+            prop_assert!(triangle.is_similar(&scaled));
+            prop_assert_eq!(triangle.canonical(), scaled.canonical());
+        }
+    }
+}
@@ -1,24 +1,51 @@
-use triangle::*;
+use std::env;
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::process;
 
-fn main() {
-    let a = get_side_from_user("a");
-    let b = get_side_from_user("b");
-    let c = get_side_from_user("c");
+use triangle::cli::{run_batch, Input, Options, ReportWriter, USAGE};
+use triangle::input::{Prompt, PromptError};
+use triangle::Triangle;
 
-    verify_triangle(a, b, c);
+const MAX_ATTEMPTS: usize = 5;
 
-    println!("Circumference is {}.", triangle_circumference(a, b, c));
-    println!("Area is {}.", triangle_area(a, b, c));
-    println!(
-        "Triangle is {}right.",
-        if is_right(a, b, c) { "" } else { "not " }
-    );
-    println!(
-        "Triangle is {}isosceles.",
-        if is_isosceles(a, b, c) { "" } else { "not " }
-    );
-    println!(
-        "Triangle is {}equilateral.",
-        if is_equilateral(a, b, c) { "" } else { "not " }
-    );
+fn read_sides_from_user() -> Result<(f64, f64, f64), PromptError> {
+    let mut prompt = Prompt::create(io::stdin().lock(), io::stdout()).with_max_attempts(MAX_ATTEMPTS);
+    Ok((prompt.read_side("a")?, prompt.read_side("b")?, prompt.read_side("c")?))
+}
+
+fn main() -> io::Result<()> {
+    let args: Vec<_> = env::args().skip(1).collect();
+    let options = Options::parse(&args).unwrap_or_else(|error| {
+        eprintln!("{}\n{}", error, USAGE);
+        process::exit(2);
+    });
+
+    let (a, b, c) = match options.input {
+        Input::Interactive => read_sides_from_user().unwrap_or_else(|error| {
+            eprintln!("{}", error);
+            process::exit(1);
+        }),
+        Input::Sides(a, b, c) => (a, b, c),
+        Input::Batch(file) => {
+            let mut writer = ReportWriter::create(io::stdout().lock(), options.format, options.precision)
+                .with_render(options.render);
+            let invalid = match file {
+                Some(file) => run_batch(BufReader::new(File::open(file)?), &mut writer, io::stderr())?,
+                None => run_batch(io::stdin().lock(), &mut writer, io::stderr())?,
+            };
+            if invalid > 0 {
+                process::exit(1);
+            }
+            return Ok(());
+        }
+    };
+
+    let triangle = Triangle::new(a, b, c).unwrap_or_else(|error| {
+        eprintln!("{}", error);
+        process::exit(1);
+    });
+    ReportWriter::create(io::stdout(), options.format, options.precision)
+        .with_render(options.render)
+        .write(&triangle)
 }
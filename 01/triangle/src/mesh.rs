@@ -0,0 +1,295 @@
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+use crate::geometry::{Point3, Triangle3};
+use crate::AngleKind;
+
+#[derive(Debug)]
+pub enum MeshError {
+    Io(io::Error),
+    /// Line which could not be parsed, numbered from 1.
+    Parse { line: usize, message: String },
+}
+
+impl fmt::Display for MeshError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MeshError::Io(error) => write!(f, "{}", error),
+            MeshError::Parse { line, message } => write!(f, "Line {}: {}", line, message),
+        }
+    }
+}
+
+impl Error for MeshError {}
+
+impl From<io::Error> for MeshError {
+    fn from(error: io::Error) -> Self {
+        MeshError::Io(error)
+    }
+}
+
+fn parse_error(line: usize, message: String) -> MeshError {
+    MeshError::Parse { line, message }
+}
+
+/// # Mesh
+/// Triangle mesh, every face is three vertices.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Mesh {
+    pub faces: Vec<[Point3; 3]>,
+}
+
+impl Mesh {
+    /// Loads a mesh from an `.obj` or ASCII `.stl` file, chosen by the extension.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Mesh, MeshError> {
+        let path = path.as_ref();
+        let input = BufReader::new(File::open(path)?);
+        let is_stl = path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("stl"));
+        if is_stl {
+            Mesh::from_stl(input)
+        } else {
+            Mesh::from_obj(input)
+        }
+    }
+
+    /// Parses vertices (`v`) and faces (`f`) of a Wavefront OBJ file, other lines are ignored.
+    /// Polygons with more than three vertices are split into triangles around their first vertex.
+    pub fn from_obj<R: BufRead>(input: R) -> Result<Mesh, MeshError> {
+        let mut vertices = Vec::new();
+        let mut faces = Vec::new();
+        for (index, line) in input.lines().enumerate() {
+            let line = line?;
+            let mut tokens = line.split_whitespace();
+            match tokens.next() {
+                Some("v") => vertices.push(parse_point(tokens, index + 1)?),
+                Some("f") => {
+                    let polygon = tokens
+                        .map(|token| parse_obj_index(token, vertices.len(), index + 1))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    if polygon.len() < 3 {
+                        return Err(parse_error(index + 1, "Face needs at least three vertices".to_string()));
+                    }
+                    for i in 1..polygon.len() - 1 {
+                        faces.push([vertices[polygon[0]], vertices[polygon[i]], vertices[polygon[i + 1]]]);
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(Mesh { faces })
+    }
+
+    /// Parses facets of an ASCII STL file.
+    pub fn from_stl<R: BufRead>(input: R) -> Result<Mesh, MeshError> {
+        let mut faces = Vec::new();
+        let mut facet = Vec::new();
+        for (index, line) in input.lines().enumerate() {
+            let line = line?;
+            let mut tokens = line.split_whitespace();
+            match tokens.next() {
+                Some("vertex") => facet.push(parse_point(tokens, index + 1)?),
+                Some("endfacet") => {
+                    if facet.len() != 3 {
+                        let message = format!("Facet has {} vertices instead of three", facet.len());
+                        return Err(parse_error(index + 1, message));
+                    }
+                    faces.push([facet[0], facet[1], facet[2]]);
+                    facet.clear();
+                }
+                _ => {}
+            }
+        }
+        Ok(Mesh { faces })
+    }
+
+    /// Runs the triangle checks on every face.
+    pub fn analyze(&self) -> MeshStatistics {
+        let mut statistics = MeshStatistics::default();
+        for &[a, b, c] in &self.faces {
+            statistics.faces += 1;
+            let triangle = match Triangle3::new(a, b, c) {
+                Ok(face) => face.triangle(),
+                Err(_) => {
+                    statistics.degenerate += 1;
+                    continue;
+                }
+            };
+            statistics.total_area += triangle.area();
+            statistics.equilateral += triangle.is_equilateral() as usize;
+            statistics.isosceles += triangle.is_isosceles() as usize;
+            match triangle.angle_kind() {
+                AngleKind::Acute => statistics.acute += 1,
+                AngleKind::Right => statistics.right += 1,
+                AngleKind::Obtuse => statistics.obtuse += 1,
+            }
+            let aspect_ratio = triangle.circumradius() / (2.0 * triangle.inradius());
+            let bucket = ASPECT_RATIO_BUCKETS
+                .iter()
+                .position(|&upper_bound| aspect_ratio < upper_bound)
+                .unwrap_or(ASPECT_RATIO_BUCKETS.len());
+            statistics.aspect_ratio_histogram[bucket] += 1;
+        }
+        statistics
+    }
+}
+
+fn parse_point<'a, I: Iterator<Item = &'a str>>(tokens: I, line: usize) -> Result<Point3, MeshError> {
+    let coordinates = tokens
+        .take(3)
+        .map(|token| token.parse::<f64>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|error| parse_error(line, error.to_string()))?;
+    match coordinates.as_slice() {
+        &[x, y, z] => Ok(Point3::new(x, y, z)),
+        _ => Err(parse_error(line, "Vertex needs three coordinates".to_string())),
+    }
+}
+
+/// Converts OBJ vertex reference like `3`, `3/1/2` or `-1` to an index into the vertices.
+fn parse_obj_index(token: &str, vertex_count: usize, line: usize) -> Result<usize, MeshError> {
+    let reference = token.split('/').next().unwrap_or_default();
+    let index: i64 = reference
+        .parse()
+        .map_err(|_| parse_error(line, format!("Invalid vertex reference '{}'", token)))?;
+    // positive references count from 1, negative ones from the last vertex
+    let index = if index < 0 {
+        vertex_count as i64 + index
+    } else {
+        index - 1
+    };
+    if index < 0 || index >= vertex_count as i64 {
+        return Err(parse_error(line, format!("Vertex {} does not exist", reference)));
+    }
+    Ok(index as usize)
+}
+
+/// Upper bounds of the aspect ratio histogram buckets, the last bucket has no upper bound.
+pub const ASPECT_RATIO_BUCKETS: [f64; 5] = [1.5, 2.0, 3.0, 5.0, 10.0];
+
+/// # MeshStatistics
+/// Aggregated results of the triangle checks on all faces of a mesh.
+///
+/// Aspect ratio of a face is its circumradius divided by its inradius doubled,
+/// so that it is 1 for an equilateral triangle and grows as the face gets thinner.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MeshStatistics {
+    pub faces: usize,
+    /// Faces whose vertices lie on one line.
+    pub degenerate: usize,
+    pub total_area: f64,
+    pub equilateral: usize,
+    pub isosceles: usize,
+    pub acute: usize,
+    pub right: usize,
+    pub obtuse: usize,
+    /// Counts of valid faces in each of the [`ASPECT_RATIO_BUCKETS`](constant.ASPECT_RATIO_BUCKETS.html).
+    pub aspect_ratio_histogram: [usize; ASPECT_RATIO_BUCKETS.len() + 1],
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SQUARE_OBJ: &str = "\
+# square split into two right triangles
+v 0 0 0
+v 2 0 0
+v 2 2 0
+v 0 2 0
+vt 0 0
+f 1/1 2/1 3/1 4/1
+";
+
+    const STL: &str = "\
+solid test
+  facet normal 0 0 1
+    outer loop
+      vertex 0 0 0
+      vertex 1 0 0
+      vertex 0.5 0.8660254037844386 0
+    endloop
+  endfacet
+  facet normal 0 0 1
+    outer loop
+      vertex 0 0 0
+      vertex 1 1 1
+      vertex 2 2 2
+    endloop
+  endfacet
+endsolid test
+";
+
+    #[test]
+    fn obj_polygons_are_split_into_triangles() {
+        let mesh = Mesh::from_obj(SQUARE_OBJ.as_bytes()).unwrap();
+        assert_eq!(mesh.faces.len(), 2);
+        assert_eq!(mesh.faces[1][2], Point3::new(0.0, 2.0, 0.0));
+    }
+
+    #[test]
+    fn obj_supports_negative_references() {
+        let mesh = Mesh::from_obj("v 0 0 0\nv 1 0 0\nv 0 1 0\nf -3 -2 -1\n".as_bytes()).unwrap();
+        assert_eq!(mesh.faces[0][0], Point3::new(0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn obj_rejects_missing_vertex() {
+        let result = Mesh::from_obj("v 0 0 0\nf 1 2 3\n".as_bytes());
+        assert_eq!(result.unwrap_err().to_string(), "Line 2: Vertex 2 does not exist");
+    }
+
+    #[test]
+    fn obj_rejects_invalid_coordinates() {
+        assert!(Mesh::from_obj("v 0 zero 0\n".as_bytes()).is_err());
+        assert!(Mesh::from_obj("v 0 0\n".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn stl_facets_are_parsed() {
+        let mesh = Mesh::from_stl(STL.as_bytes()).unwrap();
+        assert_eq!(mesh.faces.len(), 2);
+        assert_eq!(mesh.faces[1][1], Point3::new(1.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn stl_rejects_facet_without_three_vertices() {
+        let stl = "facet normal 0 0 1\nouter loop\nvertex 0 0 0\nendloop\nendfacet\n";
+        assert!(Mesh::from_stl(stl.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn analyze_classifies_faces() {
+        let statistics = Mesh::from_obj(SQUARE_OBJ.as_bytes()).unwrap().analyze();
+        assert_eq!(statistics.faces, 2);
+        assert_eq!(statistics.degenerate, 0);
+        assert!((statistics.total_area - 4.0).abs() < 1e-9);
+        assert_eq!(statistics.right, 2);
+        assert_eq!(statistics.isosceles, 2);
+        assert_eq!(statistics.equilateral, 0);
+    }
+
+    #[test]
+    fn analyze_counts_degenerate_faces() {
+        let statistics = Mesh::from_stl(STL.as_bytes()).unwrap().analyze();
+        assert_eq!(statistics.faces, 2);
+        assert_eq!(statistics.degenerate, 1);
+        assert_eq!(statistics.equilateral, 1);
+        assert_eq!(statistics.acute, 1);
+    }
+
+    #[test]
+    fn aspect_ratio_histogram() {
+        let mesh = Mesh {
+            faces: vec![
+                [Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 0.0, 0.0), Point3::new(0.5, 0.9, 0.0)],
+                [Point3::new(0.0, 0.0, 0.0), Point3::new(10.0, 0.0, 0.0), Point3::new(5.0, 0.1, 0.0)],
+            ],
+        };
+        assert_eq!(mesh.analyze().aspect_ratio_histogram, [1, 0, 0, 0, 0, 1]);
+    }
+}
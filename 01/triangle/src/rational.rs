@@ -0,0 +1,93 @@
+use std::fmt;
+
+/// Greatest common divisor by Euclid's algorithm.
+pub fn gcd(mut x: u128, mut y: u128) -> u128 {
+    while y != 0 {
+        let remainder = x % y;
+        x = y;
+        y = remainder;
+    }
+    x
+}
+
+/// # Rational
+/// Non-negative fraction, always in lowest terms.
+///
+/// # Examples
+/// ```
+/// use triangle::rational::Rational;
+///
+/// let half = Rational::new(8, 16);
+/// assert_eq!(half, Rational::new(1, 2));
+/// assert_eq!(half.to_string(), "1/2");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+    numerator: u128,
+    denominator: u128,
+}
+
+impl Rational {
+    /// # Panics
+    /// Panics if `denominator` is zero.
+    pub fn new(numerator: u128, denominator: u128) -> Rational {
+        assert_ne!(denominator, 0, "Denominator cannot be zero");
+        let divisor = gcd(numerator, denominator);
+        Rational {
+            numerator: numerator / divisor,
+            denominator: denominator / divisor,
+        }
+    }
+
+    pub fn numerator(&self) -> u128 {
+        self.numerator
+    }
+
+    pub fn denominator(&self) -> u128 {
+        self.denominator
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        self.numerator as f64 / self.denominator as f64
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.denominator == 1 {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gcd_of_numbers() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(7, 13), 1);
+        assert_eq!(gcd(0, 5), 5);
+    }
+
+    #[test]
+    fn new_reduces_to_lowest_terms() {
+        let rational = Rational::new(150, 16);
+        assert_eq!((rational.numerator(), rational.denominator()), (75, 8));
+    }
+
+    #[test]
+    fn whole_number_is_displayed_without_denominator() {
+        assert_eq!(Rational::new(32, 16).to_string(), "2");
+        assert_eq!(Rational::new(0, 16).to_string(), "0");
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_denominator_panics() {
+        Rational::new(1, 0);
+    }
+}
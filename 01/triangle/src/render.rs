@@ -0,0 +1,298 @@
+use std::fmt::Write;
+
+use crate::geometry::{self, Point2};
+use crate::{round_to, AngleKind, Side, Triangle};
+
+/// Width and height of the SVG image.
+const SVG_SIZE: f64 = 400.0;
+/// Space around the triangle for labels, in SVG units.
+const SVG_MARGIN: f64 = 50.0;
+/// Length of the sides of the right angle marker, in SVG units.
+const SVG_MARKER_SIZE: f64 = 12.0;
+
+/// Triangle laid out from its side lengths, with everything needed for labels.
+struct Drawing {
+    /// Vertices `A`, `B` and `C`.
+    vertices: [Point2; 3],
+    /// Sides `a`, `b` and `c`, opposite to the vertices.
+    sides: [f64; 3],
+    /// Angles at the vertices, in degrees.
+    angles: [f64; 3],
+    /// Index of the vertex with the right angle.
+    right_angle: Option<usize>,
+}
+
+impl Drawing {
+    fn of<T: Side>(triangle: &Triangle<T>) -> Drawing {
+        let (a, b, c) = geometry::lay_out(triangle);
+        let (side_a, side_b, side_c) = triangle.sides();
+        let (alpha, beta, gamma) = triangle.angles();
+        let angles = [alpha.to_degrees(), beta.to_degrees(), gamma.to_degrees()];
+        let right_angle = if triangle.angle_kind() == AngleKind::Right {
+            // right angle is the largest one
+            (0..3).max_by(|&i, &j| angles[i].partial_cmp(&angles[j]).unwrap())
+        } else {
+            None
+        };
+        Drawing {
+            vertices: [a, b, c],
+            sides: [side_a.to_f64(), side_b.to_f64(), side_c.to_f64()],
+            angles,
+            right_angle,
+        }
+    }
+
+    /// Ends of the side opposite to the given vertex.
+    fn side_ends(&self, vertex: usize) -> (Point2, Point2) {
+        (self.vertices[(vertex + 1) % 3], self.vertices[(vertex + 2) % 3])
+    }
+
+    fn centroid(&self) -> Point2 {
+        (self.vertices[0] + self.vertices[1] + self.vertices[2]) * (1.0 / 3.0)
+    }
+
+    /// Smallest and largest coordinates of the vertices.
+    fn bounds(&self) -> (Point2, Point2) {
+        let xs = self.vertices.iter().map(|vertex| vertex.x);
+        let ys = self.vertices.iter().map(|vertex| vertex.y);
+        (
+            Point2::new(xs.clone().fold(f64::MAX, f64::min), ys.clone().fold(f64::MAX, f64::min)),
+            Point2::new(xs.fold(f64::MIN, f64::max), ys.fold(f64::MIN, f64::max)),
+        )
+    }
+}
+
+const VERTEX_NAMES: [&str; 3] = ["A", "B", "C"];
+const SIDE_NAMES: [&str; 3] = ["a", "b", "c"];
+const ANGLE_NAMES: [&str; 3] = ["α", "β", "γ"];
+
+fn label(value: f64) -> f64 {
+    round_to(value, 2)
+}
+
+/// Moves the point by `distance` in the direction away from `center`.
+fn push_away(point: Point2, center: Point2, distance: f64) -> Point2 {
+    let direction = point - center;
+    point + direction * (distance / direction.distance(Point2::new(0.0, 0.0)))
+}
+
+/// Renders the triangle as an SVG image labeled with its sides and angles.
+/// Right angle is marked with a square.
+///
+/// # Examples
+/// ```
+/// use triangle::render::svg;
+/// use triangle::Triangle;
+///
+/// let image = svg(&Triangle::new(3, 4, 5).unwrap());
+/// assert!(image.starts_with("<svg"));
+/// assert!(image.contains("c = 5"));
+/// ```
+pub fn svg<T: Side>(triangle: &Triangle<T>) -> String {
+    let drawing = Drawing::of(triangle);
+    let (min, max) = drawing.bounds();
+    let scale = (SVG_SIZE - 2.0 * SVG_MARGIN) / (max.x - min.x).max(max.y - min.y);
+    // y axis of SVG points down
+    let to_svg = |point: Point2| {
+        Point2::new(SVG_MARGIN + (point.x - min.x) * scale, SVG_MARGIN + (max.y - point.y) * scale)
+    };
+    let vertices: Vec<_> = drawing.vertices.iter().map(|&vertex| to_svg(vertex)).collect();
+    let center = to_svg(drawing.centroid());
+
+    let mut image = String::new();
+    let size = SVG_SIZE as u32;
+    writeln!(
+        image,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{0}" viewBox="0 0 {0} {0}">"#,
+        size
+    )
+    .unwrap();
+    let points: Vec<_> = vertices.iter().map(|vertex| format!("{:.1},{:.1}", vertex.x, vertex.y)).collect();
+    writeln!(
+        image,
+        r#"  <polygon points="{}" fill="none" stroke="black" stroke-width="2"/>"#,
+        points.join(" ")
+    )
+    .unwrap();
+
+    for i in 0..3 {
+        let (start, end) = drawing.side_ends(i);
+        let middle = push_away(to_svg((start + end) * 0.5), center, 15.0);
+        writeln!(
+            image,
+            r#"  <text x="{:.1}" y="{:.1}" text-anchor="middle">{} = {}</text>"#,
+            middle.x, middle.y, SIDE_NAMES[i], label(drawing.sides[i])
+        )
+        .unwrap();
+
+        let vertex_label = push_away(vertices[i], center, 18.0);
+        writeln!(
+            image,
+            r#"  <text x="{:.1}" y="{:.1}" text-anchor="middle">{} ({} = {}°)</text>"#,
+            vertex_label.x, vertex_label.y, VERTEX_NAMES[i], ANGLE_NAMES[i], label(drawing.angles[i])
+        )
+        .unwrap();
+    }
+
+    if let Some(i) = drawing.right_angle {
+        let vertex = vertices[i];
+        let (start, end) = (vertices[(i + 1) % 3], vertices[(i + 2) % 3]);
+        let along = |point: Point2| (point - vertex) * (SVG_MARKER_SIZE / point.distance(vertex));
+        let (first, second) = (vertex + along(start), vertex + along(end));
+        let corner = first + along(end);
+        writeln!(
+            image,
+            r#"  <polyline points="{:.1},{:.1} {:.1},{:.1} {:.1},{:.1}" fill="none" stroke="black"/>"#,
+            first.x, first.y, corner.x, corner.y, second.x, second.y
+        )
+        .unwrap();
+    }
+    image.push_str("</svg>\n");
+    image
+}
+
+/// Renders the triangle as ASCII art at most `width` characters wide and `width` lines high,
+/// followed by a legend with its sides and angles. Vertices are drawn as `A`, `B` and `C`,
+/// sides are labeled by their names in the middle.
+///
+/// # Panics
+/// When the width is below 2.
+///
+/// # Examples
+/// ```
+/// use triangle::render::ascii;
+/// use triangle::Triangle;
+///
+/// let art = ascii(&Triangle::new(3, 4, 5).unwrap(), 40);
+/// assert!(art.contains("Right angle at C."));
+/// ```
+pub fn ascii<T: Side>(triangle: &Triangle<T>, width: usize) -> String {
+    // characters are about twice as tall as wide
+    const CHARACTER_ASPECT: f64 = 2.0;
+    assert!(width >= 2, "ASCII art needs to be at least 2 characters wide");
+    let max_rows = width;
+    let drawing = Drawing::of(triangle);
+    let (min, max) = drawing.bounds();
+    let scale = ((width - 1) as f64 / (max.x - min.x)).min((max_rows - 1) as f64 * CHARACTER_ASPECT / (max.y - min.y));
+    let height = ((max.y - min.y) * scale / CHARACTER_ASPECT).round() as usize + 1;
+    let to_cell = |point: Point2| {
+        let column = ((point.x - min.x) * scale).round() as usize;
+        let row = ((max.y - point.y) * scale / CHARACTER_ASPECT).round() as usize;
+        (row.min(height - 1), column.min(width - 1))
+    };
+
+    let mut grid = vec![vec![' '; width]; height];
+    for i in 0..3 {
+        let (start, end) = drawing.side_ends(i);
+        let (start, end) = (to_cell(start), to_cell(end));
+        let steps = (start.0 as i64 - end.0 as i64).abs().max((start.1 as i64 - end.1 as i64).abs()).max(1);
+        for step in 0..=steps {
+            let t = step as f64 / steps as f64;
+            let row = (start.0 as f64 + (end.0 as f64 - start.0 as f64) * t).round() as usize;
+            let column = (start.1 as f64 + (end.1 as f64 - start.1 as f64) * t).round() as usize;
+            grid[row][column] = '.';
+        }
+    }
+    for (i, name) in SIDE_NAMES.iter().enumerate() {
+        let (start, end) = drawing.side_ends(i);
+        let (row, column) = to_cell((start + end) * 0.5);
+        grid[row][column] = name.chars().next().unwrap();
+    }
+    for (i, &vertex) in drawing.vertices.iter().enumerate() {
+        let (row, column) = to_cell(vertex);
+        grid[row][column] = VERTEX_NAMES[i].chars().next().unwrap();
+    }
+
+    let mut art = String::new();
+    for row in grid {
+        let line: String = row.into_iter().collect();
+        art.push_str(line.trim_end());
+        art.push('\n');
+    }
+    for i in 0..3 {
+        writeln!(
+            art,
+            "{} = {}, {} = {}°",
+            SIDE_NAMES[i], label(drawing.sides[i]), ANGLE_NAMES[i], label(drawing.angles[i])
+        )
+        .unwrap();
+    }
+    if let Some(i) = drawing.right_angle {
+        writeln!(art, "Right angle at {}.", VERTEX_NAMES[i]).unwrap();
+    }
+    art
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn svg_contains_triangle_and_labels() {
+        let image = svg(&Triangle::new(3, 4, 5).unwrap());
+        assert!(image.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(image.ends_with("</svg>\n"));
+        assert_eq!(image.matches("<polygon").count(), 1);
+        assert!(image.contains(">a = 3<"));
+        assert!(image.contains(">b = 4<"));
+        assert!(image.contains(">c = 5<"));
+        assert!(image.contains(">C (γ = 90°)<"));
+        assert!(image.contains(">A (α = 36.87°)<"));
+    }
+
+    #[test]
+    fn svg_marks_only_right_angle() {
+        assert!(svg(&Triangle::new(3, 4, 5).unwrap()).contains("<polyline"));
+        assert!(!svg(&Triangle::new(4, 5, 6).unwrap()).contains("<polyline"));
+    }
+
+    #[test]
+    fn svg_fits_into_image() {
+        let image = svg(&Triangle::new(10.0, 10.0, 19.9).unwrap());
+        let polygon = image.lines().find(|line| line.contains("<polygon")).unwrap();
+        let points = polygon.split('"').nth(1).unwrap();
+        for coordinate in points.split([' ', ',']) {
+            let coordinate: f64 = coordinate.parse().unwrap();
+            assert!((0.0..=SVG_SIZE).contains(&coordinate));
+        }
+    }
+
+    #[test]
+    fn ascii_draws_vertices_and_sides() {
+        let art = ascii(&Triangle::new(3, 4, 5).unwrap(), 21);
+        let lines: Vec<_> = art.lines().collect();
+        // c = 5 lies on the bottom, A on the left, B on the right
+        let bottom = lines.iter().position(|line| line.starts_with('A')).unwrap();
+        assert!(lines[bottom].ends_with('B'));
+        assert!(lines[bottom].contains('c'));
+        assert!(lines[..bottom].iter().any(|line| line.contains('C')));
+        assert!(art.contains("a = 3, α = 36.87°\n"));
+        assert!(art.contains("c = 5, γ = 90°\n"));
+        assert!(art.ends_with("Right angle at C.\n"));
+    }
+
+    #[test]
+    fn ascii_of_needle_stays_small() {
+        for art in &[
+            ascii(&Triangle::new(1000, 1000, 1).unwrap(), 40),
+            ascii(&Triangle::new(1_000_000.0, 1_000_000.0, 0.001).unwrap(), 40),
+        ] {
+            let rows: Vec<_> = art.lines().take_while(|line| !line.contains(" = ")).collect();
+            assert!(rows.len() <= 40);
+            assert!(rows.iter().all(|row| row.chars().count() <= 40));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn ascii_needs_some_width() {
+        ascii(&Triangle::new(3, 4, 5).unwrap(), 1);
+    }
+
+    #[test]
+    fn ascii_without_right_angle_has_no_marker() {
+        let art = ascii(&Triangle::new(2, 2, 2).unwrap(), 20);
+        assert!(!art.contains("Right angle"));
+        assert!(art.lines().all(|line| line.chars().count() <= 20));
+    }
+}
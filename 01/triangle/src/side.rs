@@ -0,0 +1,233 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+use crate::rational::gcd;
+
+/// # Side trait
+/// Numeric type which can be used as a length of a triangle side.
+///
+/// Implemented for all primitive integers, `f32` and `f64`.
+pub trait Side: Copy + PartialOrd + fmt::Debug + fmt::Display {
+    /// Relative tolerance used when comparing values derived from sides of this type.
+    /// Zero for integers, which are compared exactly.
+    const RELATIVE_EPSILON: f64;
+
+    fn zero() -> Self;
+
+    /// Returns `None` instead of overflowing.
+    fn checked_add(self, other: Self) -> Option<Self>;
+
+    fn to_f64(self) -> f64;
+
+    fn is_finite(self) -> bool;
+
+    /// Equality which tolerates rounding errors of floating-point types.
+    fn approx_eq(self, other: Self) -> bool;
+
+    /// Compares `x² + y²` with `z²` of non-negative `x`, `y` and `z` without overflowing.
+    fn cmp_sum_of_squares(x: Self, y: Self, z: Self) -> Ordering;
+
+    /// Scales positive sides of a triangle to a representative of its shape.
+    /// Integers are divided by their greatest common divisor,
+    /// floating-point numbers are divided by the largest side.
+    fn normalize(sides: [Self; 3]) -> [Self; 3];
+}
+
+/// # IntegerSide trait
+/// Side which is an integer, so that computations with it can be exact.
+pub trait IntegerSide: Side {
+    /// Absolute value of the side.
+    fn to_u128(self) -> u128;
+}
+
+/// Compares two values computed from sides of type `T`, using its tolerance.
+pub fn approx_eq<T: Side>(x: f64, y: f64) -> bool {
+    (x - y).abs() <= T::RELATIVE_EPSILON * x.abs().max(y.abs())
+}
+
+/// Unsigned 256-bit number as `(high, low)` halves, ordered by value.
+type U256 = (u128, u128);
+
+fn square(x: u128) -> U256 {
+    const HALF: u32 = 64;
+    let (high, low) = (x >> HALF, x & u64::MAX as u128);
+    // x² = high² * 2^128 + high * low * 2^65 + low²
+    let cross = high * low;
+    let (low_sum, carry) = (low * low).overflowing_add(cross << (HALF + 1));
+    (high * high + (cross >> (HALF - 1)) + carry as u128, low_sum)
+}
+
+/// Returns `None` on overflow.
+fn checked_add_u256(x: U256, y: U256) -> Option<U256> {
+    let (low, carry) = x.1.overflowing_add(y.1);
+    let high = x.0.checked_add(y.0)?.checked_add(carry as u128)?;
+    Some((high, low))
+}
+
+fn cmp_sum_of_squares_u128(x: u128, y: u128, z: u128) -> Ordering {
+    match checked_add_u256(square(x), square(y)) {
+        Some(sum) => sum.cmp(&square(z)),
+        // z² always fits into 256 bits
+        None => Ordering::Greater,
+    }
+}
+
+macro_rules! impl_side_for_integer {
+    ($to_u128:expr; $($t:ty),*) => {
+        $(
+            impl Side for $t {
+                const RELATIVE_EPSILON: f64 = 0.0;
+
+                fn zero() -> Self {
+                    0
+                }
+
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    <$t>::checked_add(self, other)
+                }
+
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+
+                fn is_finite(self) -> bool {
+                    true
+                }
+
+                fn approx_eq(self, other: Self) -> bool {
+                    self == other
+                }
+
+                fn cmp_sum_of_squares(x: Self, y: Self, z: Self) -> Ordering {
+                    cmp_sum_of_squares_u128(x.to_u128(), y.to_u128(), z.to_u128())
+                }
+
+                fn normalize(sides: [Self; 3]) -> [Self; 3] {
+                    let divisor = sides.iter().fold(0, |divisor, side| gcd(divisor, side.to_u128()));
+                    // divisor is not larger than any of the sides, so it fits into the type
+                    let divisor = divisor as $t;
+                    [sides[0] / divisor, sides[1] / divisor, sides[2] / divisor]
+                }
+            }
+
+            impl IntegerSide for $t {
+                // the cast is a no-op for 128-bit types
+                #[allow(clippy::unnecessary_cast)]
+                fn to_u128(self) -> u128 {
+                    ($to_u128)(self)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_side_for_float {
+    ($($t:ty),*) => {
+        $(
+            impl Side for $t {
+                const RELATIVE_EPSILON: f64 = 8.0 * <$t>::EPSILON as f64;
+
+                fn zero() -> Self {
+                    0.0
+                }
+
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    let sum = self + other;
+                    if sum.is_finite() {
+                        Some(sum)
+                    } else {
+                        None
+                    }
+                }
+
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+
+                fn is_finite(self) -> bool {
+                    <$t>::is_finite(self)
+                }
+
+                fn approx_eq(self, other: Self) -> bool {
+                    approx_eq::<$t>(self as f64, other as f64)
+                }
+
+                fn normalize(sides: [Self; 3]) -> [Self; 3] {
+                    let largest = sides[0].max(sides[1]).max(sides[2]);
+                    [sides[0] / largest, sides[1] / largest, sides[2] / largest]
+                }
+
+                fn cmp_sum_of_squares(x: Self, y: Self, z: Self) -> Ordering {
+                    let (x, y, z) = (x as f64, y as f64, z as f64);
+                    let (sum, z_squared) = (x * x + y * y, z * z);
+                    if approx_eq::<$t>(sum, z_squared) {
+                        Ordering::Equal
+                    } else {
+                        sum.partial_cmp(&z_squared).unwrap_or(Ordering::Equal)
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_side_for_integer!(|x| x as u128; u8, u16, u32, u64, u128, usize);
+impl_side_for_integer!(|x| (x as i128).unsigned_abs(); i8, i16, i32, i64, i128, isize);
+impl_side_for_float!(f32, f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integer_checked_add_detects_overflow() {
+        assert_eq!(Side::checked_add(u32::MAX, 1), None);
+        assert_eq!(Side::checked_add(1u32, 2), Some(3));
+    }
+
+    #[test]
+    fn float_checked_add_detects_overflow() {
+        assert_eq!(Side::checked_add(f32::MAX, f32::MAX), None);
+        assert_eq!(Side::checked_add(1.5f32, 2.0), Some(3.5));
+    }
+
+    #[test]
+    fn floats_are_compared_with_tolerance() {
+        assert!(Side::approx_eq(0.1 + 0.2, 0.3));
+        assert!(!Side::approx_eq(0.1, 0.100001));
+    }
+
+    #[test]
+    fn square_is_exact_for_large_numbers() {
+        assert_eq!(square(u128::MAX), (u128::MAX - 1, 1));
+        assert_eq!(square(1 << 64), (1, 0));
+        assert_eq!(square(12), (0, 144));
+    }
+
+    #[test]
+    fn cmp_sum_of_squares_does_not_overflow() {
+        assert_eq!(Side::cmp_sum_of_squares(u128::MAX, u128::MAX, u128::MAX), Ordering::Greater);
+        assert_eq!(Side::cmp_sum_of_squares(1, 1, u128::MAX), Ordering::Less);
+        assert_eq!(Side::cmp_sum_of_squares(u64::MAX, u64::MAX, u64::MAX), Ordering::Greater);
+        assert_eq!(Side::cmp_sum_of_squares(3u8, 4, 5), Ordering::Equal);
+        assert_eq!(Side::cmp_sum_of_squares(30i8, 40, 50), Ordering::Equal);
+    }
+
+    #[test]
+    fn normalize_divides_integers_by_gcd() {
+        assert_eq!(Side::normalize([6u32, 8, 10]), [3, 4, 5]);
+        assert_eq!(Side::normalize([-6i8, 9, 12]), [-2, 3, 4]);
+        assert_eq!(Side::normalize([2u8, 3, 4]), [2, 3, 4]);
+    }
+
+    #[test]
+    fn normalize_divides_floats_by_largest_side() {
+        assert_eq!(Side::normalize([1.5f64, 2.0, 2.5]), [0.6, 0.8, 1.0]);
+    }
+
+    #[test]
+    fn integers_are_compared_exactly() {
+        assert!(Side::approx_eq(7u64, 7));
+        assert!(!Side::approx_eq(7u64, 8));
+    }
+}
@@ -0,0 +1,281 @@
+use std::error::Error;
+use std::f64::consts::PI;
+use std::fmt;
+
+use crate::{Triangle, TriangleError};
+
+/// # Measurements
+/// Three measurements which determine a triangle.
+///
+/// Sides `a`, `b` and `c` are opposite to angles `alpha`, `beta` and `gamma`.
+/// Angles are in radians.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Measurements {
+    /// Three sides.
+    Sss { a: f64, b: f64, c: f64 },
+    /// Two sides and the angle between them.
+    Sas { a: f64, gamma: f64, b: f64 },
+    /// Two angles and the side between them.
+    Asa { alpha: f64, c: f64, beta: f64 },
+    /// Two angles and a side opposite to one of them.
+    Aas { alpha: f64, beta: f64, a: f64 },
+    /// Two sides and an angle opposite to one of them. Can have two solutions.
+    Ssa { a: f64, b: f64, alpha: f64 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SolveError {
+    /// Angle is not in the open interval (0, π).
+    InvalidAngle(f64),
+    /// Given angles add up to π or more.
+    AnglesTooLarge,
+    /// Computed sides do not form a triangle.
+    InvalidTriangle(TriangleError),
+    /// Side opposite to the given angle is too short to reach the third side.
+    NoSolution,
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SolveError::InvalidAngle(angle) => write!(f, "Angle {} is not between 0 and π", angle),
+            SolveError::AnglesTooLarge => write!(f, "Sum of the angles must be less than π"),
+            SolveError::InvalidTriangle(error) => write!(f, "{}", error),
+            SolveError::NoSolution => write!(f, "No triangle has these measurements"),
+        }
+    }
+}
+
+impl Error for SolveError {}
+
+impl From<TriangleError> for SolveError {
+    fn from(error: TriangleError) -> Self {
+        SolveError::InvalidTriangle(error)
+    }
+}
+
+/// # Solution
+/// Solved triangle with all its properties. Angles are in radians.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Solution {
+    pub triangle: Triangle<f64>,
+    pub angles: (f64, f64, f64),
+    pub area: f64,
+    pub semiperimeter: f64,
+    pub inradius: f64,
+    pub circumradius: f64,
+    pub heights: (f64, f64, f64),
+    pub medians: (f64, f64, f64),
+}
+
+impl Solution {
+    /// Computes all properties of a triangle.
+    pub fn of(triangle: Triangle<f64>) -> Solution {
+        Solution {
+            triangle,
+            angles: triangle.angles(),
+            area: triangle.area(),
+            semiperimeter: triangle.semiperimeter(),
+            inradius: triangle.inradius(),
+            circumradius: triangle.circumradius(),
+            heights: triangle.heights(),
+            medians: triangle.medians(),
+        }
+    }
+}
+
+/// Computes missing sides and angles by the law of sines and cosines.
+///
+/// Returns one solution, or two for the ambiguous SSA case.
+///
+/// # Examples
+/// ```
+/// use triangle::solver::{solve, Measurements};
+///
+/// let solutions = solve(Measurements::Sas { a: 3.0, gamma: 90f64.to_radians(), b: 4.0 }).unwrap();
+/// assert!((solutions[0].triangle.sides().2 - 5.0).abs() < 1e-9);
+/// ```
+pub fn solve(measurements: Measurements) -> Result<Vec<Solution>, SolveError> {
+    use Measurements::*;
+    let sides = match measurements {
+        Sss { a, b, c } => vec![(a, b, c)],
+        Sas { a, gamma, b } => {
+            check_angles(&[gamma])?;
+            vec![(a, b, (a * a + b * b - 2.0 * a * b * gamma.cos()).sqrt())]
+        }
+        Asa { alpha, c, beta } => {
+            check_angles(&[alpha, beta])?;
+            let gamma = PI - alpha - beta;
+            let diameter = c / gamma.sin();
+            vec![(diameter * alpha.sin(), diameter * beta.sin(), c)]
+        }
+        Aas { alpha, beta, a } => {
+            check_angles(&[alpha, beta])?;
+            let gamma = PI - alpha - beta;
+            let diameter = a / alpha.sin();
+            vec![(a, diameter * beta.sin(), diameter * gamma.sin())]
+        }
+        Ssa { a, b, alpha } => {
+            check_angles(&[alpha])?;
+            solve_ssa(a, b, alpha)?
+        }
+    };
+    sides
+        .into_iter()
+        .map(|(a, b, c)| Ok(Solution::of(Triangle::new(a, b, c)?)))
+        .collect()
+}
+
+fn check_angles(angles: &[f64]) -> Result<(), SolveError> {
+    if let Some(&angle) = angles.iter().find(|&&angle| !(angle > 0.0 && angle < PI)) {
+        return Err(SolveError::InvalidAngle(angle));
+    }
+    if angles.iter().sum::<f64>() >= PI {
+        return Err(SolveError::AnglesTooLarge);
+    }
+    Ok(())
+}
+
+/// Sides of all triangles with sides `a`, `b` and angle `alpha` opposite to `a`.
+fn solve_ssa(a: f64, b: f64, alpha: f64) -> Result<Vec<(f64, f64, f64)>, SolveError> {
+    const EPSILON: f64 = 1e-12;
+    let sin_beta = b * alpha.sin() / a;
+    if sin_beta > 1.0 + EPSILON {
+        return Err(SolveError::NoSolution);
+    }
+    let betas = if sin_beta >= 1.0 - EPSILON {
+        vec![PI / 2.0]
+    } else {
+        let acute_beta = sin_beta.asin();
+        vec![acute_beta, PI - acute_beta]
+    };
+    let sides: Vec<_> = betas
+        .into_iter()
+        .filter(|beta| alpha + beta < PI - EPSILON)
+        .map(|beta| (a, b, a * (PI - alpha - beta).sin() / alpha.sin()))
+        .collect();
+    if sides.is_empty() {
+        return Err(SolveError::NoSolution);
+    }
+    Ok(sides)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-9, "{} is not {}", actual, expected);
+    }
+
+    fn assert_sides(solution: &Solution, expected: (f64, f64, f64)) {
+        let (a, b, c) = solution.triangle.sides();
+        assert_close(a, expected.0);
+        assert_close(b, expected.1);
+        assert_close(c, expected.2);
+    }
+
+    #[test]
+    fn sss_computes_all_properties() {
+        let solutions = solve(Measurements::Sss { a: 3.0, b: 4.0, c: 5.0 }).unwrap();
+        assert_eq!(solutions.len(), 1);
+        let solution = &solutions[0];
+        assert_close(solution.angles.2, 90f64.to_radians());
+        assert_close(solution.area, 6.0);
+        assert_close(solution.semiperimeter, 6.0);
+        assert_close(solution.inradius, 1.0);
+        assert_close(solution.circumradius, 2.5);
+        assert_close(solution.heights.2, 2.4);
+        assert_close(solution.medians.2, 2.5);
+    }
+
+    #[test]
+    fn sss_rejects_invalid_triangle() {
+        assert!(matches!(
+            solve(Measurements::Sss { a: 1.0, b: 2.0, c: 3.0 }),
+            Err(SolveError::InvalidTriangle(_))
+        ));
+    }
+
+    #[test]
+    fn sas_computes_third_side() {
+        let solutions = solve(Measurements::Sas { a: 3.0, gamma: 90f64.to_radians(), b: 4.0 }).unwrap();
+        assert_sides(&solutions[0], (3.0, 4.0, 5.0));
+    }
+
+    #[test]
+    fn asa_computes_two_sides() {
+        let solutions = solve(Measurements::Asa {
+            alpha: 60f64.to_radians(),
+            c: 2.0,
+            beta: 60f64.to_radians(),
+        })
+        .unwrap();
+        assert_sides(&solutions[0], (2.0, 2.0, 2.0));
+    }
+
+    #[test]
+    fn aas_computes_two_sides() {
+        let solutions = solve(Measurements::Aas {
+            alpha: 30f64.to_radians(),
+            beta: 60f64.to_radians(),
+            a: 1.0,
+        })
+        .unwrap();
+        assert_sides(&solutions[0], (1.0, 3f64.sqrt(), 2.0));
+    }
+
+    #[test]
+    fn ssa_ambiguous_case_has_two_solutions() {
+        let solutions = solve(Measurements::Ssa { a: 6.0, b: 8.0, alpha: 30f64.to_radians() }).unwrap();
+        assert_eq!(solutions.len(), 2);
+        for solution in &solutions {
+            assert_close(solution.angles.0, 30f64.to_radians());
+            assert_close(solution.triangle.sides().0, 6.0);
+            assert_close(solution.triangle.sides().1, 8.0);
+        }
+        assert!(solutions[0].angles.1 < solutions[1].angles.1);
+    }
+
+    #[test]
+    fn ssa_with_right_angle_has_one_solution() {
+        let solutions = solve(Measurements::Ssa { a: 1.0, b: 2.0, alpha: 30f64.to_radians() }).unwrap();
+        assert_eq!(solutions.len(), 1);
+        assert_close(solutions[0].angles.1, 90f64.to_radians());
+    }
+
+    #[test]
+    fn ssa_with_long_opposite_side_has_one_solution() {
+        let solutions = solve(Measurements::Ssa { a: 5.0, b: 3.0, alpha: 90f64.to_radians() }).unwrap();
+        assert_eq!(solutions.len(), 1);
+        assert_sides(&solutions[0], (5.0, 3.0, 4.0));
+    }
+
+    #[test]
+    fn ssa_with_short_opposite_side_has_no_solution() {
+        assert_eq!(
+            solve(Measurements::Ssa { a: 1.0, b: 5.0, alpha: 30f64.to_radians() }),
+            Err(SolveError::NoSolution)
+        );
+    }
+
+    #[test]
+    fn angles_must_be_between_zero_and_pi() {
+        assert_eq!(
+            solve(Measurements::Sas { a: 1.0, gamma: PI, b: 1.0 }),
+            Err(SolveError::InvalidAngle(PI))
+        );
+        assert_eq!(
+            solve(Measurements::Aas { alpha: -1.0, beta: 1.0, a: 1.0 }),
+            Err(SolveError::InvalidAngle(-1.0))
+        );
+    }
+
+    #[test]
+    fn angles_must_leave_room_for_the_third_angle() {
+        assert_eq!(
+            solve(Measurements::Asa { alpha: 2.0, c: 1.0, beta: 2.0 }),
+            Err(SolveError::AnglesTooLarge)
+        );
+    }
+}
@@ -0,0 +1,240 @@
+use crate::rational::gcd;
+use crate::{IntegerSide, Triangle};
+
+/// # PythagoreanTriples
+/// Iterator over primitive Pythagorean triples with hypotenuse up to a bound,
+/// generated by walking Berggren's tree. Triples are not ordered by size.
+///
+/// # Examples
+/// ```
+/// use triangle::triples::PythagoreanTriples;
+///
+/// let mut hypotenuses: Vec<_> = PythagoreanTriples::up_to(30).map(|triangle| triangle.sides().2).collect();
+/// hypotenuses.sort();
+/// assert_eq!(hypotenuses, vec![5, 13, 17, 25, 29]);
+/// ```
+pub struct PythagoreanTriples {
+    bound: u64,
+    stack: Vec<(i128, i128, i128)>,
+}
+
+impl PythagoreanTriples {
+    pub fn up_to(bound: u64) -> PythagoreanTriples {
+        PythagoreanTriples {
+            bound,
+            stack: vec![(3, 4, 5)],
+        }
+    }
+}
+
+impl Iterator for PythagoreanTriples {
+    /// Right triangle with sides `a < b < c`.
+    type Item = Triangle<u64>;
+
+    fn next(&mut self) -> Option<Triangle<u64>> {
+        loop {
+            let (a, b, c) = self.stack.pop()?;
+            if c > self.bound as i128 {
+                // children have even longer hypotenuses
+                continue;
+            }
+            self.stack.extend_from_slice(&[
+                (a - 2 * b + 2 * c, 2 * a - b + 2 * c, 2 * a - 2 * b + 3 * c),
+                (a + 2 * b + 2 * c, 2 * a + b + 2 * c, 2 * a + 2 * b + 3 * c),
+                (-a + 2 * b + 2 * c, -2 * a + b + 2 * c, -2 * a + 2 * b + 3 * c),
+            ]);
+            let (a, b) = (a.min(b) as u64, a.max(b) as u64);
+            return Some(Triangle::new(a, b, c as u64).expect("Pythagorean triple is a triangle"));
+        }
+    }
+}
+
+/// # HeronianTriangles
+/// Iterator over triangles with integer sides up to a bound and integer area,
+/// ordered by the largest side. Filter them by [`is_primitive`](fn.is_primitive.html)
+/// to keep only the primitive ones.
+///
+/// # Examples
+/// ```
+/// use triangle::triples::HeronianTriangles;
+///
+/// let triangles: Vec<_> = HeronianTriangles::up_to(6).map(|triangle| triangle.sides()).collect();
+/// assert_eq!(triangles, vec![(3, 4, 5), (5, 5, 6)]);
+/// ```
+pub struct HeronianTriangles {
+    bound: u64,
+    a: u64,
+    b: u64,
+    c: u64,
+}
+
+impl HeronianTriangles {
+    pub fn up_to(bound: u64) -> HeronianTriangles {
+        HeronianTriangles { bound, a: 0, b: 1, c: 1 }
+    }
+
+    /// Moves to the next triangle with sides `a <= b <= c`.
+    fn advance(&mut self) -> Option<()> {
+        self.a += 1;
+        if self.a > self.b {
+            self.b += 1;
+            if self.b > self.c {
+                self.c += 1;
+                self.b = self.c / 2 + 1;
+            }
+            // a + b > c
+            self.a = self.c - self.b + 1;
+        }
+        if self.c > self.bound {
+            None
+        } else {
+            Some(())
+        }
+    }
+}
+
+impl Iterator for HeronianTriangles {
+    /// Triangle with sides `a <= b <= c`.
+    type Item = Triangle<u64>;
+
+    fn next(&mut self) -> Option<Triangle<u64>> {
+        loop {
+            self.advance()?;
+            let triangle = Triangle::new(self.a, self.b, self.c).expect("sides satisfy the triangle inequality");
+            if area_is_integer(&triangle) {
+                return Some(triangle);
+            }
+        }
+    }
+}
+
+fn area_is_integer<T: IntegerSide>(triangle: &Triangle<T>) -> bool {
+    match triangle.area_squared_exact() {
+        Some(area_squared) => area_squared.denominator() == 1 && is_square(area_squared.numerator()),
+        None => false,
+    }
+}
+
+fn is_square(number: u128) -> bool {
+    let root = integer_sqrt(number);
+    root * root == number
+}
+
+/// Largest integer whose square is at most the number, computed exactly by Newton's method.
+/// A floating-point root has only 53 bits and can be off by far more than one for large numbers.
+fn integer_sqrt(number: u128) -> u128 {
+    if number < 2 {
+        return number;
+    }
+    // start above the root, from there the iteration decreases until it reaches it
+    let bits = 128 - number.leading_zeros();
+    let mut root = 1u128 << bits.div_ceil(2);
+    loop {
+        let next = (root + number / root) / 2;
+        if next >= root {
+            return root;
+        }
+        root = next;
+    }
+}
+
+/// Tells whether the sides have no common divisor other than 1.
+pub fn is_primitive<T: IntegerSide>(triangle: &Triangle<T>) -> bool {
+    let (a, b, c) = triangle.sides();
+    gcd(gcd(a.to_u128(), b.to_u128()), c.to_u128()) == 1
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::AngleKind;
+
+    #[test]
+    fn pythagorean_triples_up_to_100() {
+        let triples: HashSet<_> = PythagoreanTriples::up_to(100).map(|triangle| triangle.sides()).collect();
+        assert_eq!(triples.len(), 16);
+        assert!(triples.contains(&(3, 4, 5)));
+        assert!(triples.contains(&(65, 72, 97)));
+        assert!(!triples.contains(&(6, 8, 10)));
+    }
+
+    #[test]
+    fn bound_is_inclusive() {
+        assert_eq!(PythagoreanTriples::up_to(4).count(), 0);
+        assert_eq!(PythagoreanTriples::up_to(5).count(), 1);
+    }
+
+    #[test]
+    fn all_pythagorean_triples_are_right_and_primitive() {
+        for triangle in PythagoreanTriples::up_to(100_000) {
+            assert_eq!(triangle.angle_kind(), AngleKind::Right, "{:?}", triangle);
+            assert!(is_primitive(&triangle), "{:?}", triangle);
+        }
+    }
+
+    #[test]
+    fn multiples_of_pythagorean_triples_are_right() {
+        for triangle in PythagoreanTriples::up_to(1000) {
+            let (a, b, c) = triangle.sides();
+            for k in 2..50 {
+                assert!(Triangle::new(k * a, k * b, k * c).unwrap().is_right());
+                assert!(!Triangle::new(k * a, k * b, k * c - 1).unwrap().is_right());
+            }
+        }
+    }
+
+    #[test]
+    fn pythagorean_triples_have_integer_area() {
+        for triangle in PythagoreanTriples::up_to(10_000) {
+            let (a, b, _) = triangle.sides();
+            assert_eq!(triangle.area(), (a * b / 2) as f64);
+            assert!(area_is_integer(&triangle));
+        }
+    }
+
+    #[test]
+    fn heronian_triangles_up_to_20() {
+        let triangles: Vec<_> = HeronianTriangles::up_to(20).map(|triangle| triangle.sides()).collect();
+        assert!(triangles.contains(&(13, 14, 15)));
+        assert!(triangles.contains(&(6, 8, 10)));
+        assert!(triangles.contains(&(5, 5, 8)));
+        assert!(!triangles.contains(&(2, 3, 4)));
+        assert!(triangles.windows(2).all(|pair| pair[0].2 <= pair[1].2));
+    }
+
+    #[test]
+    fn heronian_triangles_have_integer_area() {
+        for triangle in HeronianTriangles::up_to(200) {
+            let area = triangle.area();
+            assert_eq!(area, area.round(), "{:?}", triangle);
+        }
+    }
+
+    #[test]
+    fn heronian_triangles_include_all_pythagorean_triples() {
+        let heronian: HashSet<_> = HeronianTriangles::up_to(150).collect();
+        assert!(PythagoreanTriples::up_to(150).all(|triangle| heronian.contains(&triangle)));
+    }
+
+    #[test]
+    fn primitive_heronian_triangles() {
+        let primitive = HeronianTriangles::up_to(20).filter(is_primitive).count();
+        assert_eq!(primitive, 12);
+    }
+
+    #[test]
+    fn is_square_of_large_numbers() {
+        let root = u64::MAX as u128;
+        assert!(is_square(root * root));
+        assert!(!is_square(root * root - 1));
+        assert!(is_square(0));
+        assert!(!is_square(2));
+        // too far from a power of two for the floating-point root to be close
+        let root = (1u128 << 63) + 12345;
+        assert!(is_square(root * root));
+        assert!(!is_square(root * root + 1));
+        assert_eq!(integer_sqrt(root * root - 1), root - 1);
+    }
+}
@@ -0,0 +1,49 @@
+use std::env;
+use std::process;
+
+use turn_based_game::replay;
+use turn_based_game::ruleset::Ruleset;
+
+const USAGE: &str = "Usage: replay <log file> [--rules <rules file>]";
+
+fn usage_error(message: &str) -> ! {
+    eprintln!("{}\n{}", message, USAGE);
+    process::exit(2);
+}
+
+fn main() {
+    let mut log_file = None;
+    let mut rules_file = None;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--rules" => match args.next() {
+                Some(file) => rules_file = Some(file),
+                None => usage_error("Missing rules file"),
+            },
+            _ if log_file.is_none() && !arg.starts_with("--") => log_file = Some(arg),
+            _ => usage_error(&format!("Unexpected argument '{}'", arg)),
+        }
+    }
+    let log_file = log_file.unwrap_or_else(|| usage_error("Missing log file"));
+    // a different ruleset shows how a change of the rules affects the recorded game
+    let ruleset = rules_file.map(|file| {
+        Ruleset::load(&file).unwrap_or_else(|error| {
+            eprintln!("{}", error);
+            process::exit(1);
+        })
+    });
+
+    match replay::replay_file(&log_file, ruleset.as_ref()) {
+        Ok(game) => {
+            println!("Replay of {} matches the recording, final resources:", log_file);
+            for player in &game.state().players {
+                println!("{}: {:?}", player.name, player.resources);
+            }
+        }
+        Err(error) => {
+            eprintln!("{}", error);
+            process::exit(1);
+        }
+    }
+}
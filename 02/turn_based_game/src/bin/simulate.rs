@@ -0,0 +1,34 @@
+use std::env;
+use std::process;
+
+use turn_based_game::ruleset::Ruleset;
+use turn_based_game::simulation::{self, Options, StrategyKind, USAGE};
+
+fn main() {
+    let args: Vec<_> = env::args().skip(1).collect();
+    let options = Options::parse(&args).unwrap_or_else(|error| {
+        eprintln!("{}\n{}", error, USAGE);
+        process::exit(2);
+    });
+    let ruleset = Ruleset::load(&options.rules_file).unwrap_or_else(|error| {
+        eprintln!("{}", error);
+        process::exit(1);
+    });
+    for kind in &options.strategies {
+        if let StrategyKind::Script(build_order) = kind {
+            if let Some(name) = build_order.iter().find(|name| ruleset.building(name).is_none()) {
+                eprintln!("Building '{}' does not exist!", name);
+                process::exit(1);
+            }
+        }
+    }
+
+    println!(
+        "Simulating {} games of {} turns with seed {}.",
+        options.games, options.turns, options.seed
+    );
+    for kind in &options.strategies {
+        let report = simulation::simulate(&ruleset, kind, options.games, options.seed, options.turns);
+        println!("\nStrategy {}:\n{}", kind.create(options.seed).name(), report);
+    }
+}
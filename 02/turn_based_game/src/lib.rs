@@ -0,0 +1,1331 @@
+use std::collections::BTreeSet;
+use std::fmt;
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use map::{Building, Map, Position};
+use market::{Commodity, Market};
+use resources::Resources;
+use ruleset::{PopulationRules, RandomEvent, Ruleset, Technology, VictoryCondition};
+
+pub mod map;
+pub mod market;
+pub mod replay;
+pub mod resources;
+pub mod ruleset;
+pub mod save;
+pub mod score;
+pub mod simulation;
+pub mod strategy;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GameAction {
+    /// Builds the building type with the given name on the tile, see
+    /// [`Game::building_sites`](struct.Game.html#method.building_sites).
+    Build(String, Position),
+    Harvest,
+    Mine(ResourceType),
+    /// Reveals some of the unexplored tiles next to the explored ones.
+    Explore,
+    /// Starts researching the technology, it is finished after its turns of research.
+    Research(String),
+    /// Sells the amount of the first commodity for the second one at the market prices.
+    Trade(Commodity, u32, Commodity),
+    /// Ends the turn of the player with the action points which are left unused.
+    /// The turn also ends when the player cannot pay for any other action.
+    EndTurn,
+}
+
+/// Resource which can be mined, the amount is given by the [mining rules](ruleset/struct.MiningRules.html).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResourceType {
+    Gold,
+    Wood,
+}
+
+impl fmt::Display for ResourceType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ResourceType::Gold => write!(f, "gold"),
+            ResourceType::Wood => write!(f, "wood"),
+        }
+    }
+}
+
+/// What happened in the game, for frontends to show to the player.
+/// Events without a player belong to the player on turn.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Event {
+    Built(String),
+    /// Crops brought in from the fields to the stored food.
+    Harvested(u32),
+    /// Amount mined, zero when the mines are depleted.
+    Mined(ResourceType, u32),
+    /// Number of newly explored tiles.
+    Explored(usize),
+    /// Amount of the first commodity sold for the amount of the second one.
+    Traded(Commodity, u32, Commodity, u32),
+    /// People who died because there was not enough food for them.
+    Starved(u32),
+    /// People born because there was plenty of food.
+    PopulationGrew(u32),
+    /// Building produced nothing because its upkeep could not be paid.
+    UpkeepNotPaid(String),
+    /// Random event with the given description happened.
+    Happened(String),
+    ResearchStarted(String),
+    /// Technology was researched, its buildings and upgrades are available.
+    Researched(String),
+    /// Research did not advance because its cost could not be paid.
+    ResearchNotPaid(String),
+    /// Player on turn ended the turn early.
+    TurnEnded,
+    /// Turn with the given number, counted from 1, started.
+    TurnStarted(u32),
+    /// Player with the given index, counted from 0, is on turn.
+    PlayerTurnStarted(usize),
+    /// See [`Game::outcome`](struct.Game.html#method.outcome) for the result of every player.
+    GameEnded,
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Event::Built(name) => write!(f, "Building {}...", name.to_lowercase()),
+            Event::Harvested(amount) => write!(f, "Harvesting {} food...", amount),
+            Event::Mined(resource_type, 0) => write!(f, "There is no {} left to mine!", resource_type),
+            Event::Mined(resource_type, amount) => write!(f, "Mining {} {}...", amount, resource_type),
+            Event::Explored(0) => write!(f, "There is nothing left to explore!"),
+            Event::Explored(tiles) => write!(f, "Exploring {} new tiles...", tiles),
+            Event::Traded(sold, amount, bought, received) => {
+                write!(f, "Trading {} {} for {} {}...", amount, sold, received, bought)
+            }
+            Event::Starved(people) => write!(f, "{} people starved to death!", people),
+            Event::PopulationGrew(people) => write!(f, "{} people were born.", people),
+            Event::UpkeepNotPaid(name) => write!(f, "Not enough resources to maintain {}!", name),
+            Event::Happened(description) => write!(f, "{}", description),
+            Event::ResearchStarted(name) => write!(f, "Starting research of {}...", name),
+            Event::Researched(name) => write!(f, "{} has been researched!", name),
+            Event::ResearchNotPaid(name) => write!(f, "Not enough resources to continue research of {}!", name),
+            Event::TurnEnded => write!(f, "Turn ended."),
+            Event::TurnStarted(turn) => write!(f, "Turn {} started.", turn),
+            Event::PlayerTurnStarted(player) => write!(f, "Player {} is on turn.", player + 1),
+            Event::GameEnded => write!(f, "Game ended."),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    /// Victory condition which was reached.
+    Won(VictoryCondition),
+    Lost(Defeat),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Defeat {
+    /// Everybody starved to death.
+    Starvation,
+    /// No victory condition was reached by the last turn.
+    OutOfTime,
+    /// Player with the given name reached a victory condition first.
+    Outpaced(String),
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Outcome::Won(condition) => write!(f, "You won, you managed to {}!", condition),
+            Outcome::Lost(Defeat::Starvation) => write!(f, "You lost, everybody starved to death!"),
+            Outcome::Lost(Defeat::OutOfTime) => write!(f, "You lost, you ran out of time!"),
+            Outcome::Lost(Defeat::Outpaced(winner)) => write!(f, "You lost, {} was faster!", winner),
+        }
+    }
+}
+
+/// Reason why an action was rejected. Rejected actions change nothing.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RuleError {
+    NotEnoughResources,
+    /// Action costs more action points than the player has left in this turn.
+    NotEnoughActionPoints,
+    UnknownBuilding(String),
+    /// Tile is not explored, not free or not next to the territory of the player.
+    InvalidSite(Position),
+    /// Trade would give nothing, because the commodities are the same or the amount is too small.
+    InvalidTrade,
+    UnknownTechnology(String),
+    /// Technology which has to be researched first.
+    MissingTechnology(String),
+    AlreadyResearched(String),
+    /// Only one technology can be researched at a time, this one is being researched.
+    ResearchInProgress(String),
+    GameOver,
+}
+
+impl fmt::Display for RuleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RuleError::NotEnoughResources => write!(f, "Not enough resources!"),
+            RuleError::NotEnoughActionPoints => write!(f, "Not enough action points left in this turn!"),
+            RuleError::UnknownBuilding(name) => write!(f, "Building '{}' does not exist!", name),
+            RuleError::InvalidSite((x, y)) => write!(f, "You cannot build at {},{}!", x, y),
+            RuleError::InvalidTrade => write!(f, "Nothing would be traded!"),
+            RuleError::UnknownTechnology(name) => write!(f, "Technology '{}' does not exist!", name),
+            RuleError::MissingTechnology(name) => write!(f, "You have to research {} first!", name),
+            RuleError::AlreadyResearched(name) => write!(f, "{} is already researched!", name),
+            RuleError::ResearchInProgress(name) => write!(f, "You are still researching {}!", name),
+            RuleError::GameOver => write!(f, "Game has already ended!"),
+        }
+    }
+}
+
+impl std::error::Error for RuleError {}
+
+/// Everything about a game in progress which is saved, the ruleset is not.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameState {
+    /// Number of finished turns. In every turn, each player spends their action points.
+    pub current_turn: u32,
+    pub end_turn: u32,
+    /// Seed of all random numbers in the game, so that the same actions always give the same game.
+    pub seed: u64,
+    pub map: Map,
+    pub market: Market,
+    /// Index of the player on turn.
+    pub current_player: usize,
+    pub players: Vec<PlayerState>,
+}
+
+/// # PlayerState
+/// Everything one player owns.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlayerState {
+    pub name: String,
+    /// Name of the [strategy](strategy/fn.by_name.html) playing for a computer player, `None` for a human.
+    #[serde(default)]
+    pub strategy: Option<String>,
+    pub resources: Resources,
+    pub buildings: Vec<Building>,
+    /// Tile of the town of the player, it belongs to the territory together with the buildings.
+    pub home: Position,
+    /// Tiles which the player has seen.
+    pub explored: BTreeSet<Position>,
+    pub population: u32,
+    /// Food grown by farms which has not been harvested yet.
+    pub crops: u32,
+    /// Gold and wood left in the mines of the player.
+    pub deposits: Resources,
+    /// Names of the finished technologies.
+    pub researched: BTreeSet<String>,
+    pub research: Option<Research>,
+    /// Action points left in the current turn of the player.
+    pub action_points: u32,
+}
+
+/// Technology being researched.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Research {
+    pub technology: String,
+    /// Turns of research paid so far.
+    pub turns_done: u32,
+}
+
+impl PlayerState {
+    /// Human player with the starting resources and population of the ruleset.
+    /// The home is given when the player joins a [game](struct.Game.html).
+    pub fn new(name: &str, ruleset: &Ruleset) -> PlayerState {
+        PlayerState {
+            name: name.to_string(),
+            strategy: None,
+            resources: ruleset.starting_resources,
+            buildings: Vec::new(),
+            home: (0, 0),
+            explored: BTreeSet::new(),
+            population: ruleset.population.initial,
+            crops: 0,
+            deposits: ruleset.mining.deposits,
+            researched: BTreeSet::new(),
+            research: None,
+            action_points: ruleset.actions.points_per_turn,
+        }
+    }
+
+    /// Tiles of the home and of the buildings.
+    pub fn territory(&self) -> impl Iterator<Item = Position> + '_ {
+        std::iter::once(self.home).chain(self.buildings.iter().map(|building| building.position))
+    }
+
+    /// Action points of the player in every turn, with the extra points of the buildings.
+    pub fn action_points_per_turn(&self, ruleset: &Ruleset) -> u32 {
+        ruleset.action_points(self.buildings.iter().map(|building| building.name.as_str()))
+    }
+
+    fn do_start_of_turn_actions(&mut self, ruleset: &Ruleset, rng: &mut StdRng, events: &mut Vec<Event>) {
+        self.action_points = self.action_points_per_turn(ruleset);
+        for building in &self.buildings {
+            let building = ruleset.building(&building.name).expect("built buildings are in the ruleset");
+            if self.resources.pay(&building.upkeep) {
+                let production = ruleset.production(building, &self.researched);
+                self.resources.gold = self.resources.gold.saturating_add(production.gold);
+                self.resources.wood = self.resources.wood.saturating_add(production.wood);
+                self.crops = self.crops.saturating_add(production.food);
+            } else {
+                events.push(Event::UpkeepNotPaid(building.name.clone()));
+            }
+        }
+        self.do_research(ruleset, events);
+        for random_event in &ruleset.events {
+            if rng.gen_bool(random_event.chance) {
+                self.suffer(random_event);
+                events.push(Event::Happened(random_event.description.clone()));
+            }
+        }
+        self.feed_population(&ruleset.population, events);
+    }
+
+    fn do_research(&mut self, ruleset: &Ruleset, events: &mut Vec<Event>) {
+        let research = match &mut self.research {
+            Some(research) => research,
+            None => return,
+        };
+        let technology = ruleset
+            .technology(&research.technology)
+            .expect("researched technologies are in the ruleset");
+        if !self.resources.pay(&technology.cost_per_turn) {
+            events.push(Event::ResearchNotPaid(technology.name.clone()));
+            return;
+        }
+        research.turns_done += 1;
+        if research.turns_done >= technology.turns {
+            self.research = None;
+            self.researched.insert(technology.name.clone());
+            events.push(Event::Researched(technology.name.clone()));
+        }
+    }
+
+    fn suffer(&mut self, random_event: &RandomEvent) {
+        let change = i64::from(self.crops) * i64::from(random_event.crops_percent) / 100;
+        self.crops = (i64::from(self.crops) + change).min(i64::from(u32::MAX)) as u32;
+        self.resources.lose_percent(&random_event.lost_percent);
+        self.resources.add(&random_event.gained);
+    }
+
+    /// Everyone eats, people who cannot eat starve. When there is food for another meal,
+    /// the population grows.
+    fn feed_population(&mut self, rules: &PopulationRules, events: &mut Vec<Event>) {
+        let food = &mut self.resources.food;
+        let needed = self.population.saturating_mul(rules.food_per_person);
+        if *food >= needed {
+            *food -= needed;
+            if rules.growth > 0 && self.population > 0 && *food >= needed {
+                self.population = self.population.saturating_add(rules.growth);
+                events.push(Event::PopulationGrew(rules.growth));
+            }
+        } else {
+            let fed = *food / rules.food_per_person;
+            *food -= fed * rules.food_per_person;
+            let starved = self.population - fed;
+            self.population = fed;
+            events.push(Event::Starved(starved));
+        }
+    }
+}
+
+/// What the random numbers are used for, so that different uses in the same turn differ.
+#[derive(Clone, Copy)]
+enum RandomUse {
+    Mining = 1,
+    Events = 2,
+    Exploring = 3,
+}
+
+/// # Game
+/// Rules of the game without any input or output, so that it can be driven
+/// by a terminal, a test or a simulation alike.
+///
+/// # Examples
+/// ```
+/// use turn_based_game::ruleset::Ruleset;
+/// use turn_based_game::{Event, Game, GameAction, ResourceType};
+///
+/// let ruleset = Ruleset::from_json(r#"{"buildings": [{"name": "Farm"}], "population": {"initial": 0}}"#).unwrap();
+/// let mut game = Game::new(ruleset, 10);
+/// let events = game.apply(GameAction::Mine(ResourceType::Gold)).unwrap();
+/// assert_eq!(
+///     events,
+///     vec![Event::Mined(ResourceType::Gold, 500), Event::TurnStarted(2), Event::PlayerTurnStarted(0)]
+/// );
+/// assert_eq!(game.player(0).resources.gold, 500);
+/// ```
+pub struct Game {
+    ruleset: Ruleset,
+    state: GameState,
+    /// Players as they were before the game started, so that it can be started again with a seed.
+    new_players: Option<Vec<PlayerState>>,
+    /// Events of the start of the first turn, which happened before any action.
+    start_events: Vec<Event>,
+}
+
+impl Game {
+    /// Game of a single human player.
+    pub fn new(ruleset: Ruleset, end_turn: u32) -> Game {
+        let player = PlayerState::new("Player", &ruleset);
+        Game::with_players(ruleset, vec![player], end_turn)
+    }
+
+    /// Game in which the players take turns in the given order, the turn of the first player
+    /// has already started.
+    ///
+    /// # Panics
+    /// When there are no players or more players than the map has columns,
+    /// see [`Ruleset::max_players`](ruleset/struct.Ruleset.html#method.max_players).
+    pub fn with_players(ruleset: Ruleset, players: Vec<PlayerState>, end_turn: u32) -> Game {
+        assert!(!players.is_empty(), "game needs at least one player");
+        assert!(players.len() <= ruleset.max_players(), "every player needs a home of their own");
+        Game::start(ruleset, players, end_turn, 0)
+    }
+
+    /// Starts the game again with the seed of the random numbers and the map,
+    /// games with the same seed and actions are the same.
+    ///
+    /// # Panics
+    /// When the game was loaded instead of created.
+    pub fn with_seed(self, seed: u64) -> Game {
+        let players = self.new_players.expect("only created games can be seeded");
+        Game::start(self.ruleset, players, self.state.end_turn, seed)
+    }
+
+    fn start(ruleset: Ruleset, players: Vec<PlayerState>, end_turn: u32, seed: u64) -> Game {
+        let map = Map::generate(&ruleset.map, seed, players.len());
+        let state = GameState {
+            current_turn: 0,
+            end_turn,
+            seed,
+            map,
+            market: Market::new(&ruleset.market),
+            current_player: 0,
+            players: players.clone(),
+        };
+        let mut game = Game {
+            ruleset,
+            state,
+            new_players: Some(players),
+            start_events: Vec::new(),
+        };
+        game.place_players();
+        let mut events = Vec::new();
+        if game.is_eliminated(0) || !game.start_turn(&mut events) {
+            game.pass_turn(&mut events);
+        }
+        game.start_events = events;
+        game
+    }
+
+    /// Puts the players to their homes, they see the tiles around.
+    fn place_players(&mut self) {
+        let map = &self.state.map;
+        let count = self.state.players.len();
+        for (index, player) in self.state.players.iter_mut().enumerate() {
+            player.home = map.home(index, count);
+            player.explored = map.around(player.home, self.ruleset.map.sight).into_iter().collect();
+        }
+    }
+
+    /// Continues a saved game, see [`save::load`](save/fn.load.html).
+    pub fn from_state(ruleset: Ruleset, state: GameState) -> Game {
+        Game {
+            ruleset,
+            state,
+            new_players: None,
+            start_events: Vec::new(),
+        }
+    }
+
+    /// Events of the start of the first turn, which frontends show before the first action.
+    /// Empty for loaded games, their events were shown when they happened.
+    pub fn start_events(&self) -> &[Event] {
+        &self.start_events
+    }
+
+    pub fn ruleset(&self) -> &Ruleset {
+        &self.ruleset
+    }
+
+    pub fn state(&self) -> &GameState {
+        &self.state
+    }
+
+    /// Index of the player on turn.
+    pub fn current_player(&self) -> usize {
+        self.state.current_player
+    }
+
+    pub fn player(&self, index: usize) -> &PlayerState {
+        &self.state.players[index]
+    }
+
+    /// Explored free tiles next to the territory of the player, where the player can build.
+    pub fn building_sites(&self, index: usize) -> Vec<Position> {
+        let player = self.player(index);
+        let mut sites: Vec<_> = player
+            .territory()
+            .flat_map(|position| self.state.map.around(position, 1))
+            .filter(|position| player.explored.contains(position) && !self.is_occupied(*position))
+            .collect();
+        sites.sort_unstable();
+        sites.dedup();
+        sites
+    }
+
+    fn is_occupied(&self, position: Position) -> bool {
+        self.state.players.iter().any(|player| player.territory().any(|tile| tile == position))
+    }
+
+    /// Percent added to the mining yield of the player by the explored terrain next to the territory.
+    pub fn mining_bonus(&self, index: usize) -> Resources {
+        let player = self.player(index);
+        let tiles: BTreeSet<_> = player
+            .territory()
+            .flat_map(|position| self.state.map.around(position, 1))
+            .filter(|position| player.explored.contains(position))
+            .collect();
+        let mut bonus = Resources::default();
+        for terrain in tiles.into_iter().filter_map(|position| self.state.map.terrain(position)) {
+            if let Some(rules) = self.ruleset.map.terrain.iter().find(|rules| rules.terrain == terrain) {
+                bonus.add(&rules.mining_bonus);
+            }
+        }
+        bonus
+    }
+
+    /// Building has no technology to unlock it or the player researched one of them.
+    pub fn is_unlocked(&self, index: usize, building: &str) -> bool {
+        let researched = &self.player(index).researched;
+        let mut technologies = self.ruleset.unlocked_by(building).peekable();
+        technologies.peek().is_none() || technologies.any(|technology| researched.contains(&technology.name))
+    }
+
+    /// Technologies which the player has not researched yet but all their prerequisites,
+    /// without the one being researched, in the order of the ruleset.
+    pub fn available_technologies(&self, index: usize) -> Vec<&Technology> {
+        let player = self.player(index);
+        let in_progress = |technology: &Technology| {
+            player.research.as_ref().is_some_and(|research| research.technology == technology.name)
+        };
+        self.ruleset
+            .technologies
+            .iter()
+            .filter(|technology| !player.researched.contains(&technology.name) && !in_progress(technology))
+            .filter(|technology| technology.requires.iter().all(|name| player.researched.contains(name)))
+            .collect()
+    }
+
+    /// Player lost all people and takes no more turns.
+    pub fn is_eliminated(&self, index: usize) -> bool {
+        self.ruleset.population.initial > 0 && self.player(index).population == 0
+    }
+
+    /// Game ends when somebody reaches a victory condition, after the last turn
+    /// or when everybody starved.
+    pub fn is_over(&self) -> bool {
+        self.winner().is_some()
+            || self.state.current_turn >= self.state.end_turn
+            || (0..self.state.players.len()).all(|index| self.is_eliminated(index))
+    }
+
+    /// Result of the game for the given player, or `None` while it goes on.
+    pub fn outcome(&self, index: usize) -> Option<Outcome> {
+        if !self.is_over() {
+            return None;
+        }
+        if self.is_eliminated(index) {
+            return Some(Outcome::Lost(Defeat::Starvation));
+        }
+        if let Some((winner, condition)) = self.winner() {
+            if winner == index {
+                return Some(Outcome::Won(condition.clone()));
+            }
+            return Some(Outcome::Lost(Defeat::Outpaced(self.player(winner).name.clone())));
+        }
+        let survive = VictoryCondition::Survive;
+        if self.ruleset.victory.contains(&survive) && self.is_met(self.player(index), &survive) {
+            return Some(Outcome::Won(survive));
+        }
+        Some(Outcome::Lost(Defeat::OutOfTime))
+    }
+
+    /// First player who reached a victory condition other than survival, with the condition.
+    fn winner(&self) -> Option<(usize, &VictoryCondition)> {
+        self.state.players.iter().enumerate().find_map(|(index, player)| {
+            self.ruleset
+                .victory
+                .iter()
+                .find(|condition| **condition != VictoryCondition::Survive && self.is_met(player, condition))
+                .map(|condition| (index, condition))
+        })
+    }
+
+    fn is_met(&self, player: &PlayerState, condition: &VictoryCondition) -> bool {
+        match condition {
+            VictoryCondition::Resources { target } => player.resources.can_afford(target),
+            VictoryCondition::Buildings { building, count } => {
+                player.buildings.iter().filter(|built| built.name == *building).count() >= *count as usize
+            }
+            VictoryCondition::Population { target } => player.population >= *target,
+            VictoryCondition::Survive => self.state.current_turn >= self.state.end_turn && player.population > 0,
+        }
+    }
+
+    /// Action points paid for the action.
+    pub fn action_cost(&self, action: &GameAction) -> u32 {
+        let costs = &self.ruleset.actions.costs;
+        match action {
+            GameAction::Build(..) => costs.build,
+            GameAction::Harvest => costs.harvest,
+            GameAction::Mine(_) => costs.mine,
+            GameAction::Explore => costs.explore,
+            GameAction::Research(_) => costs.research,
+            GameAction::Trade(..) => costs.trade,
+            GameAction::EndTurn => 0,
+        }
+    }
+
+    /// Does the action of the player on turn. When the player cannot pay for another action,
+    /// the turn passes to the next player and the start of the next turn is included in the events.
+    pub fn apply(&mut self, action: GameAction) -> Result<Vec<Event>, RuleError> {
+        if self.is_over() {
+            return Err(RuleError::GameOver);
+        }
+        let cost = self.action_cost(&action);
+        if self.player(self.state.current_player).action_points < cost {
+            return Err(RuleError::NotEnoughActionPoints);
+        }
+        let mut events = vec![self.do_action(action)?];
+
+        let player = &mut self.state.players[self.state.current_player];
+        player.action_points -= cost;
+        if events[0] == Event::TurnEnded || player.action_points < self.ruleset.actions.costs.cheapest() {
+            self.pass_turn(&mut events);
+        }
+        if self.is_over() {
+            events.push(Event::GameEnded);
+        }
+        Ok(events)
+    }
+
+    /// Moves to the next player who has not been eliminated. The turn ends when the last player is done.
+    fn pass_turn(&mut self, events: &mut Vec<Event>) {
+        loop {
+            let next = (self.state.current_player + 1) % self.state.players.len();
+            if next == 0 {
+                self.state.current_turn += 1;
+            }
+            self.state.current_player = next;
+            if self.is_over() {
+                return;
+            }
+            if next == 0 {
+                self.state.market.recover(&self.ruleset.market);
+                events.push(Event::TurnStarted(self.state.current_turn + 1));
+            }
+            if !self.is_eliminated(next) && self.start_turn(events) {
+                return;
+            }
+        }
+    }
+
+    /// Starts the turn of the player on turn. Returns `false` when the player starved
+    /// at the start of the turn and the game goes on without them.
+    fn start_turn(&mut self, events: &mut Vec<Event>) -> bool {
+        let player = self.state.current_player;
+        events.push(Event::PlayerTurnStarted(player));
+        let mut rng = self.rng(RandomUse::Events);
+        self.state.players[player].do_start_of_turn_actions(&self.ruleset, &mut rng, events);
+        self.is_over() || !self.is_eliminated(player)
+    }
+
+    fn do_action(&mut self, action: GameAction) -> Result<Event, RuleError> {
+        match action {
+            GameAction::Build(name, position) => self.do_build_action(name, position),
+            GameAction::Harvest => Ok(self.do_harvest_action()),
+            GameAction::Mine(resource_type) => Ok(self.do_mine_action(resource_type)),
+            GameAction::Explore => Ok(self.do_explore_action()),
+            GameAction::Research(name) => self.do_research_action(name),
+            GameAction::Trade(sold, amount, bought) => self.do_trade_action(sold, amount, bought),
+            GameAction::EndTurn => Ok(Event::TurnEnded),
+        }
+    }
+
+    /// Random numbers for the player on turn. They depend only on the seed, the turn, the player,
+    /// the action points the player has left and the use, so a loaded game continues the same way
+    /// as the saved one would. Every action costs points, so actions of the same turn get different numbers.
+    fn rng(&self, random_use: RandomUse) -> StdRng {
+        let turn = u64::from(self.state.current_turn).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+        let player = (self.state.current_player as u64 + 1).wrapping_mul(0xC2B2_AE3D_27D4_EB4F);
+        let points = self.player(self.state.current_player).action_points;
+        let points = (u64::from(points) + 1).wrapping_mul(0x1656_67B1_9E37_79F9);
+        StdRng::seed_from_u64(self.state.seed ^ turn ^ player ^ points ^ random_use as u64)
+    }
+
+    fn do_build_action(&mut self, name: String, position: Position) -> Result<Event, RuleError> {
+        let building = self
+            .ruleset
+            .building(&name)
+            .ok_or_else(|| RuleError::UnknownBuilding(name.clone()))?;
+        if !self.is_unlocked(self.state.current_player, &name) {
+            let technology = self.ruleset.unlocked_by(&name).next().expect("locked building has a technology");
+            return Err(RuleError::MissingTechnology(technology.name.clone()));
+        }
+        if !self.building_sites(self.state.current_player).contains(&position) {
+            return Err(RuleError::InvalidSite(position));
+        }
+        let player = &mut self.state.players[self.state.current_player];
+        if !player.resources.pay(&building.cost) {
+            return Err(RuleError::NotEnoughResources);
+        }
+        player.buildings.push(Building {
+            name: name.clone(),
+            position,
+        });
+        Ok(Event::Built(name))
+    }
+
+    /// People bring in as many crops as they can carry.
+    fn do_harvest_action(&mut self) -> Event {
+        let player = &mut self.state.players[self.state.current_player];
+        let capacity = player.population.saturating_mul(self.ruleset.population.harvest_per_person);
+        let amount = player.crops.min(capacity);
+        player.crops -= amount;
+        player.resources.food = player.resources.food.saturating_add(amount);
+        Event::Harvested(amount)
+    }
+
+    /// Mines a random amount increased by the terrain bonus, at most what is left in the mines.
+    fn do_mine_action(&mut self, resource_type: ResourceType) -> Event {
+        let mining = &self.ruleset.mining;
+        let amount = self.rng(RandomUse::Mining).gen_range(mining.min_yield, mining.max_yield.saturating_add(1));
+        let bonus = self.mining_bonus(self.state.current_player);
+        let player = &mut self.state.players[self.state.current_player];
+        use ResourceType::*;
+        let (deposit, stock, bonus) = match resource_type {
+            Gold => (&mut player.deposits.gold, &mut player.resources.gold, bonus.gold),
+            Wood => (&mut player.deposits.wood, &mut player.resources.wood, bonus.wood),
+        };
+        let amount = (u64::from(amount) * (100 + u64::from(bonus)) / 100).min(u64::from(*deposit)) as u32;
+        *deposit -= amount;
+        *stock = stock.saturating_add(amount);
+        Event::Mined(resource_type, amount)
+    }
+
+    /// Research is paid and advances at the start of the turns of the player.
+    fn do_research_action(&mut self, name: String) -> Result<Event, RuleError> {
+        let technology = self
+            .ruleset
+            .technology(&name)
+            .ok_or_else(|| RuleError::UnknownTechnology(name.clone()))?;
+        let player = &mut self.state.players[self.state.current_player];
+        if player.researched.contains(&name) {
+            return Err(RuleError::AlreadyResearched(name));
+        }
+        if let Some(research) = &player.research {
+            return Err(RuleError::ResearchInProgress(research.technology.clone()));
+        }
+        if let Some(missing) = technology.requires.iter().find(|required| !player.researched.contains(*required)) {
+            return Err(RuleError::MissingTechnology(missing.clone()));
+        }
+        player.research = Some(Research {
+            technology: name.clone(),
+            turns_done: 0,
+        });
+        Ok(Event::ResearchStarted(name))
+    }
+
+    /// Trades at the prices before the trade, the prices move afterwards.
+    fn do_trade_action(&mut self, sold: Commodity, amount: u32, bought: Commodity) -> Result<Event, RuleError> {
+        let market = &mut self.state.market;
+        let received = market.quote(sold, amount, bought);
+        if sold == bought || received == 0 {
+            return Err(RuleError::InvalidTrade);
+        }
+        let resources = &mut self.state.players[self.state.current_player].resources;
+        let stock = sold.amount_mut(resources);
+        if *stock < amount {
+            return Err(RuleError::NotEnoughResources);
+        }
+        *stock -= amount;
+        let stock = bought.amount_mut(resources);
+        *stock = stock.saturating_add(received);
+        market.trade(&self.ruleset.market, (sold, amount), (bought, received));
+        Ok(Event::Traded(sold, amount, bought, received))
+    }
+
+    /// Reveals random tiles next to the explored ones.
+    fn do_explore_action(&mut self) -> Event {
+        let mut rng = self.rng(RandomUse::Exploring);
+        let map = &self.state.map;
+        let player = &mut self.state.players[self.state.current_player];
+        let frontier: BTreeSet<_> = player
+            .explored
+            .iter()
+            .flat_map(|position| map.around(*position, 1))
+            .filter(|position| !player.explored.contains(position))
+            .collect();
+        let mut frontier: Vec<_> = frontier.into_iter().collect();
+        frontier.shuffle(&mut rng);
+        frontier.truncate(self.ruleset.map.explore_tiles);
+        player.explored.extend(&frontier);
+        Event::Explored(frontier.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::map::Terrain;
+    use crate::ruleset::{ActionCosts, ActionRules, MapRules, MiningRules, TerrainRules};
+
+    fn ruleset(population: PopulationRules, starting_food: u32) -> Ruleset {
+        let mut ruleset = Ruleset::from_json(include_str!("../rules.json")).unwrap();
+        ruleset.population = population;
+        ruleset.starting_resources = Resources {
+            food: starting_food,
+            ..Resources::default()
+        };
+        ruleset.victory = vec![VictoryCondition::Survive];
+        // fixed yields and no surprises, unless a test wants them
+        ruleset.mining = MiningRules::default();
+        ruleset.map = MapRules::default();
+        ruleset.events = Vec::new();
+        ruleset.actions = ActionRules::default();
+        ruleset
+    }
+
+    fn game_with_population(end_turn: u32, population: PopulationRules, starting_food: u32) -> Game {
+        Game::new(ruleset(population, starting_food), end_turn)
+    }
+
+    /// Single worker who does not need to eat.
+    fn worker() -> PopulationRules {
+        PopulationRules {
+            initial: 1,
+            food_per_person: 0,
+            growth: 0,
+            harvest_per_person: 50,
+        }
+    }
+
+    fn game(end_turn: u32) -> Game {
+        game_with_population(end_turn, worker(), 0)
+    }
+
+    fn two_player_game(end_turn: u32) -> Game {
+        let ruleset = ruleset(worker(), 0);
+        let players = vec![PlayerState::new("Alice", &ruleset), PlayerState::new("Bob", &ruleset)];
+        Game::with_players(ruleset, players, end_turn)
+    }
+
+    /// Builds on the first free site of the player on turn.
+    fn build(game: &Game, name: &str) -> GameAction {
+        GameAction::Build(name.to_string(), game.building_sites(game.current_player())[0])
+    }
+
+    fn mine(game: &mut Game, gold_turns: u32, wood_turns: u32) {
+        for _ in 0..gold_turns {
+            game.apply(GameAction::Mine(ResourceType::Gold)).unwrap();
+        }
+        for _ in 0..wood_turns {
+            game.apply(GameAction::Mine(ResourceType::Wood)).unwrap();
+        }
+    }
+
+    #[test]
+    fn mining_adds_resources_and_ends_turn() {
+        let mut game = game(10);
+        mine(&mut game, 2, 1);
+        assert_eq!(game.player(0).resources, Resources { gold: 1000, wood: 500, food: 0 });
+        assert_eq!(game.state().current_turn, 3);
+    }
+
+    #[test]
+    fn farm_grows_crops_until_harvested() {
+        let mut game = game(10);
+        mine(&mut game, 2, 1);
+        let events = game.apply(build(&game, "Farm")).unwrap();
+        assert_eq!(
+            events,
+            vec![Event::Built("Farm".to_string()), Event::TurnStarted(5), Event::PlayerTurnStarted(0)]
+        );
+        assert_eq!(game.player(0).crops, 100);
+        assert_eq!(game.player(0).resources.food, 0);
+
+        // one worker carries only 50
+        let events = game.apply(GameAction::Harvest).unwrap();
+        assert_eq!(events[0], Event::Harvested(50));
+        assert_eq!(game.player(0).resources.food, 50);
+        assert_eq!(game.player(0).crops, 150);
+    }
+
+    #[test]
+    fn population_eats_grows_and_starves() {
+        let population = PopulationRules {
+            initial: 2,
+            food_per_person: 10,
+            growth: 1,
+            harvest_per_person: 50,
+        };
+        let mut game = game_with_population(10, population, 45);
+        // people eat and grow at the start of the first turn too
+        assert_eq!((game.player(0).population, game.player(0).resources.food), (3, 25));
+
+        let events = game.apply(GameAction::Harvest).unwrap();
+        assert!(events.contains(&Event::Starved(1)));
+        assert_eq!((game.player(0).population, game.player(0).resources.food), (2, 5));
+    }
+
+    #[test]
+    fn population_growth_saturates() {
+        let population = PopulationRules {
+            initial: 2,
+            food_per_person: 0,
+            growth: u32::MAX,
+            harvest_per_person: 50,
+        };
+        let mut game = game_with_population(10, population, 0);
+        game.apply(GameAction::Harvest).unwrap();
+        assert_eq!(game.player(0).population, u32::MAX);
+    }
+
+    #[test]
+    fn rejected_action_changes_nothing() {
+        let mut game = game(10);
+        mine(&mut game, 1, 0);
+        let before = game.state().clone();
+        assert_eq!(
+            game.apply(build(&game, "Farm")),
+            Err(RuleError::NotEnoughResources)
+        );
+        assert_eq!(
+            game.apply(build(&game, "Castle")),
+            Err(RuleError::UnknownBuilding("Castle".to_string()))
+        );
+        assert_eq!(game.state(), &before);
+    }
+
+    #[test]
+    fn unpaid_upkeep_stops_production() {
+        let mut game = game(10);
+        mine(&mut game, 1, 1);
+        game.apply(build(&game, "Sawmill")).unwrap();
+        // sawmill needs food which nobody produces
+        assert_eq!(game.player(0).resources.wood, 300);
+        let events = game.apply(GameAction::Harvest).unwrap();
+        assert!(events.contains(&Event::UpkeepNotPaid("Sawmill".to_string())));
+        assert_eq!(game.player(0).resources.wood, 300);
+    }
+
+    #[test]
+    fn research_takes_turns_and_upgrades_production() {
+        let mut game = game(20);
+        mine(&mut game, 2, 1);
+        let events = game.apply(GameAction::Research("Irrigation".to_string())).unwrap();
+        assert_eq!(events[0], Event::ResearchStarted("Irrigation".to_string()));
+        game.apply(GameAction::Harvest).unwrap();
+        let events = game.apply(GameAction::Harvest).unwrap();
+        assert!(events.contains(&Event::Researched("Irrigation".to_string())));
+        assert_eq!(game.player(0).resources.gold, 700);
+        assert!(game.player(0).researched.contains("Irrigation"));
+        assert_eq!(game.player(0).research, None);
+
+        mine(&mut game, 1, 0);
+        game.apply(build(&game, "Farm")).unwrap();
+        assert_eq!(game.player(0).crops, 150);
+    }
+
+    #[test]
+    fn research_stalls_without_resources() {
+        let mut game = game(10);
+        let events = game.apply(GameAction::Research("Irrigation".to_string())).unwrap();
+        assert!(events.contains(&Event::ResearchNotPaid("Irrigation".to_string())));
+        assert_eq!(game.player(0).research.as_ref().unwrap().turns_done, 0);
+    }
+
+    #[test]
+    fn technologies_need_prerequisites() {
+        let mut game = game(10);
+        mine(&mut game, 2, 1);
+        let research = |name: &str| GameAction::Research(name.to_string());
+        assert_eq!(
+            game.apply(build(&game, "Workshop")),
+            Err(RuleError::MissingTechnology("Carpentry".to_string()))
+        );
+        assert_eq!(
+            game.apply(research("Deep Mining")),
+            Err(RuleError::MissingTechnology("Carpentry".to_string()))
+        );
+        assert_eq!(
+            game.apply(research("Alchemy")),
+            Err(RuleError::UnknownTechnology("Alchemy".to_string()))
+        );
+
+        game.apply(research("Carpentry")).unwrap();
+        assert_eq!(
+            game.apply(research("Irrigation")),
+            Err(RuleError::ResearchInProgress("Carpentry".to_string()))
+        );
+        let available: Vec<_> = game.available_technologies(0).iter().map(|technology| technology.name.clone()).collect();
+        assert_eq!(available, vec!["Irrigation"]);
+
+        game.apply(GameAction::Harvest).unwrap();
+        assert!(game.is_unlocked(0, "Workshop"));
+        assert_eq!(
+            game.apply(research("Carpentry")),
+            Err(RuleError::AlreadyResearched("Carpentry".to_string()))
+        );
+        game.apply(build(&game, "Workshop")).unwrap();
+    }
+
+    #[test]
+    fn trading_exchanges_at_market_prices() {
+        let mut game = game(10);
+        mine(&mut game, 1, 0);
+        let prices = game.state().market.prices;
+        let trade = GameAction::Trade(Commodity::Gold, 100, Commodity::Food);
+        let events = game.apply(trade).unwrap();
+        assert_eq!(events[0], Event::Traded(Commodity::Gold, 100, Commodity::Food, 200));
+        assert_eq!(game.player(0).resources, Resources { gold: 400, wood: 0, food: 200 });
+        // prices moved and recovered a little at the start of the next turn
+        let market = &game.state().market;
+        assert!(market.prices.gold < prices.gold);
+        assert!(market.prices.food > prices.food);
+
+        let events = game.apply(GameAction::Trade(Commodity::Gold, 100, Commodity::Food)).unwrap();
+        assert!(matches!(events[0], Event::Traded(_, 100, _, received) if received < 200));
+    }
+
+    #[test]
+    fn invalid_trades_are_rejected() {
+        let mut game = game(10);
+        mine(&mut game, 1, 0);
+        let before = game.state().clone();
+        assert_eq!(
+            game.apply(GameAction::Trade(Commodity::Gold, 600, Commodity::Wood)),
+            Err(RuleError::NotEnoughResources)
+        );
+        assert_eq!(
+            game.apply(GameAction::Trade(Commodity::Gold, 100, Commodity::Gold)),
+            Err(RuleError::InvalidTrade)
+        );
+        assert_eq!(
+            game.apply(GameAction::Trade(Commodity::Gold, 0, Commodity::Wood)),
+            Err(RuleError::InvalidTrade)
+        );
+        assert_eq!(game.state(), &before);
+    }
+
+    #[test]
+    fn actions_are_paid_with_action_points() {
+        let mut game = game(10);
+        game.ruleset.actions = ActionRules {
+            points_per_turn: 3,
+            costs: ActionCosts {
+                build: 2,
+                ..ActionCosts::default()
+            },
+        };
+        game.state.players[0].action_points = 3;
+        mine(&mut game, 2, 0);
+        assert_eq!((game.state().current_turn, game.player(0).action_points), (0, 1));
+        // the last point is not enough to build
+        game.state.players[0].resources.wood = 500;
+        assert_eq!(game.apply(build(&game, "Farm")), Err(RuleError::NotEnoughActionPoints));
+
+        let events = game.apply(GameAction::Mine(ResourceType::Wood)).unwrap();
+        assert_eq!(events[1], Event::TurnStarted(2));
+        assert_eq!(game.player(0).action_points, 3);
+        let events = game.apply(build(&game, "Farm")).unwrap();
+        assert_eq!(events, vec![Event::Built("Farm".to_string())]);
+        assert_eq!(game.player(0).action_points, 1);
+    }
+
+    #[test]
+    fn end_turn_passes_to_next_player() {
+        let mut game = two_player_game(10);
+        game.ruleset.actions.points_per_turn = 2;
+        game.state.players[0].action_points = 2;
+        let events = game.apply(GameAction::EndTurn).unwrap();
+        assert_eq!(events[..2], [Event::TurnEnded, Event::PlayerTurnStarted(1)]);
+        assert_eq!(game.current_player(), 1);
+        assert_eq!(game.player(1).action_points, 2);
+    }
+
+    #[test]
+    fn buildings_grant_action_points() {
+        let mut game = game(10);
+        game.state.players[0].resources = Resources { gold: 1000, wood: 1000, food: 100 };
+        game.apply(build(&game, "Town Hall")).unwrap();
+        assert_eq!(game.player(0).action_points, 2);
+        game.apply(GameAction::Harvest).unwrap();
+        assert_eq!(game.state().current_turn, 1);
+        game.apply(GameAction::Harvest).unwrap();
+        assert_eq!(game.state().current_turn, 2);
+    }
+
+    #[test]
+    fn game_ends_at_end_turn() {
+        let mut game = game(2);
+        game.apply(GameAction::Harvest).unwrap();
+        assert_eq!(game.apply(GameAction::Harvest).unwrap(), vec![Event::Harvested(0), Event::GameEnded]);
+        assert!(game.is_over());
+        assert_eq!(game.outcome(0), Some(Outcome::Won(VictoryCondition::Survive)));
+        assert_eq!(game.apply(GameAction::Harvest), Err(RuleError::GameOver));
+    }
+
+    #[test]
+    fn reaching_victory_condition_ends_game_early() {
+        let mut game = game(10);
+        game.ruleset.victory = vec![VictoryCondition::Resources {
+            target: Resources { gold: 1000, wood: 0, food: 0 },
+        }];
+        mine(&mut game, 1, 0);
+        assert_eq!(game.outcome(0), None);
+        let events = game.apply(GameAction::Mine(ResourceType::Gold)).unwrap();
+        assert_eq!(events.last(), Some(&Event::GameEnded));
+        assert!(matches!(game.outcome(0), Some(Outcome::Won(VictoryCondition::Resources { .. }))));
+        assert_eq!(game.state().current_turn, 2);
+    }
+
+    #[test]
+    fn game_is_lost_without_victory_by_last_turn() {
+        let mut game = game(1);
+        game.ruleset.victory = vec![VictoryCondition::Population { target: 100 }];
+        game.apply(GameAction::Harvest).unwrap();
+        assert_eq!(game.outcome(0), Some(Outcome::Lost(Defeat::OutOfTime)));
+    }
+
+    #[test]
+    fn game_is_lost_when_everybody_starves() {
+        let population = PopulationRules {
+            initial: 2,
+            food_per_person: 10,
+            growth: 0,
+            harvest_per_person: 50,
+        };
+        // food for the first turn only
+        let mut game = game_with_population(10, population, 20);
+        let events = game.apply(GameAction::Harvest).unwrap();
+        assert_eq!(events.last(), Some(&Event::GameEnded));
+        assert_eq!(game.outcome(0), Some(Outcome::Lost(Defeat::Starvation)));
+    }
+
+    #[test]
+    fn mining_yield_is_random_but_seeded() {
+        let play = |seed| {
+            let mut ruleset = ruleset(worker(), 0);
+            ruleset.mining = MiningRules {
+                min_yield: 300,
+                max_yield: 700,
+                ..MiningRules::default()
+            };
+            let mut game = Game::new(ruleset, 10).with_seed(seed);
+            mine(&mut game, 5, 0);
+            game.player(0).resources.gold
+        };
+        assert_eq!(play(1), play(1));
+        assert!((5 * 300..=5 * 700).contains(&play(1)));
+        assert!((0..10).any(|seed| play(seed) != play(1)));
+    }
+
+    #[test]
+    fn actions_of_the_same_turn_get_different_numbers() {
+        let mine_twice = |seed| {
+            let mut ruleset = ruleset(worker(), 0);
+            ruleset.mining = MiningRules {
+                min_yield: 300,
+                max_yield: 700,
+                ..MiningRules::default()
+            };
+            ruleset.actions.points_per_turn = 2;
+            let mut game = Game::new(ruleset, 10).with_seed(seed);
+            game.state.players[0].action_points = 2;
+            let first = game.apply(GameAction::Mine(ResourceType::Gold)).unwrap();
+            let second = game.apply(GameAction::Mine(ResourceType::Gold)).unwrap();
+            assert_eq!(game.state().current_turn, 1);
+            (first[0].clone(), second[0].clone())
+        };
+        assert!((0..10).map(mine_twice).any(|(first, second)| first != second));
+    }
+
+    #[test]
+    fn depleted_mines_give_nothing() {
+        let mut game = game(10);
+        game.state.players[0].deposits.wood = 700;
+        mine(&mut game, 0, 1);
+        let events = game.apply(GameAction::Mine(ResourceType::Wood)).unwrap();
+        assert_eq!(events[0], Event::Mined(ResourceType::Wood, 200));
+        let events = game.apply(GameAction::Mine(ResourceType::Wood)).unwrap();
+        assert_eq!(events[0], Event::Mined(ResourceType::Wood, 0));
+        assert_eq!(game.player(0).resources.wood, 700);
+    }
+
+    #[test]
+    fn random_events_happen_at_start_of_turn() {
+        let mut game = game(10);
+        mine(&mut game, 2, 1);
+        game.apply(build(&game, "Farm")).unwrap();
+        game.ruleset.events = vec![RandomEvent {
+            name: "Drought".to_string(),
+            description: "Drought!".to_string(),
+            chance: 1.0,
+            crops_percent: -50,
+            lost_percent: Resources::default(),
+            gained: Resources { gold: 10, wood: 0, food: 0 },
+        }];
+        let events = game.apply(GameAction::Mine(ResourceType::Gold)).unwrap();
+        assert!(events.contains(&Event::Happened("Drought!".to_string())));
+        // the farm produced before the drought came
+        assert_eq!(game.player(0).crops, 100);
+        assert_eq!(game.player(0).resources.gold, 510);
+    }
+
+    #[test]
+    fn buildings_need_free_explored_site_next_to_territory() {
+        let mut game = game(10);
+        mine(&mut game, 2, 1);
+        let home = game.player(0).home;
+        let far = (home.0 + 3, home.1);
+        for &position in &[home, far, (100, 100)] {
+            assert_eq!(
+                game.apply(GameAction::Build("Farm".to_string(), position)),
+                Err(RuleError::InvalidSite(position))
+            );
+        }
+        let site = game.building_sites(0)[0];
+        game.apply(GameAction::Build("Farm".to_string(), site)).unwrap();
+        assert!(!game.building_sites(0).contains(&site));
+        assert_eq!(game.player(0).buildings[0].position, site);
+    }
+
+    #[test]
+    fn exploring_reveals_tiles_next_to_explored_ones() {
+        let mut game = game(10);
+        let explored = game.player(0).explored.clone();
+        assert_eq!(explored.len(), 9);
+        let events = game.apply(GameAction::Explore).unwrap();
+        assert_eq!(events[0], Event::Explored(3));
+        let new: Vec<_> = game.player(0).explored.difference(&explored).copied().collect();
+        assert_eq!(new.len(), 3);
+        for position in new {
+            assert!(game.state().map.around(position, 1).iter().any(|tile| explored.contains(tile)));
+        }
+    }
+
+    #[test]
+    fn adjacent_terrain_increases_mining_yield() {
+        let mut game = game(10);
+        game.ruleset.map.terrain.push(TerrainRules {
+            terrain: Terrain::Forest,
+            weight: 0,
+            mining_bonus: Resources { gold: 0, wood: 10, food: 0 },
+        });
+        let home = game.player(0).home;
+        let width = game.state.map.width;
+        for &(x, y) in &[(home.0 + 1, home.1), (home.0 - 1, home.1)] {
+            game.state.map.tiles[y * width + x] = Terrain::Forest;
+        }
+        assert_eq!(game.mining_bonus(0).wood, 20);
+        let events = game.apply(GameAction::Mine(ResourceType::Wood)).unwrap();
+        assert_eq!(events[0], Event::Mined(ResourceType::Wood, 600));
+    }
+
+    #[test]
+    fn players_take_turns_in_order() {
+        let mut game = two_player_game(10);
+        let events = game.apply(GameAction::Mine(ResourceType::Gold)).unwrap();
+        assert_eq!(events[1..], [Event::PlayerTurnStarted(1)]);
+        let events = game.apply(GameAction::Mine(ResourceType::Wood)).unwrap();
+        assert_eq!(events[1..], [Event::TurnStarted(2), Event::PlayerTurnStarted(0)]);
+        assert_eq!(game.player(0).resources.gold, 500);
+        assert_eq!(game.player(1).resources.wood, 500);
+        assert_eq!(game.state().current_turn, 1);
+    }
+
+    #[test]
+    fn first_player_reaching_victory_wins() {
+        let mut game = two_player_game(10);
+        game.ruleset.victory = vec![VictoryCondition::Resources {
+            target: Resources { gold: 0, wood: 500, food: 0 },
+        }];
+        game.apply(GameAction::Harvest).unwrap();
+        let events = game.apply(GameAction::Mine(ResourceType::Wood)).unwrap();
+        assert_eq!(events.last(), Some(&Event::GameEnded));
+        assert_eq!(game.outcome(0), Some(Outcome::Lost(Defeat::Outpaced("Bob".to_string()))));
+        assert!(matches!(game.outcome(1), Some(Outcome::Won(_))));
+    }
+
+    #[test]
+    fn smallest_map_has_a_home_for_every_player() {
+        let mut ruleset = ruleset(worker(), 0);
+        ruleset.map = MapRules {
+            width: 3,
+            height: 4,
+            ..MapRules::default()
+        };
+        let players = (0..3).map(|index| PlayerState::new(&index.to_string(), &ruleset)).collect();
+        let game = Game::with_players(ruleset, players, 10);
+        let homes: BTreeSet<_> = game.state().players.iter().map(|player| player.home).collect();
+        assert_eq!(homes.len(), 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn players_need_a_column_each() {
+        let mut ruleset = ruleset(worker(), 0);
+        ruleset.map.width = 2;
+        let players = (0..3).map(|index| PlayerState::new(&index.to_string(), &ruleset)).collect();
+        Game::with_players(ruleset, players, 10);
+    }
+
+    #[test]
+    fn starved_players_are_skipped() {
+        let population = PopulationRules {
+            initial: 1,
+            food_per_person: 10,
+            growth: 0,
+            harvest_per_person: 50,
+        };
+        let ruleset = ruleset(population, 0);
+        let alice = PlayerState::new("Alice", &ruleset);
+        let mut bob = PlayerState::new("Bob", &ruleset);
+        bob.resources.food = 1000;
+        let carol = PlayerState::new("Carol", &ruleset);
+        let mut game = Game::with_players(ruleset, vec![alice, bob, carol], 10);
+
+        // Alice starves at the start of the game, before doing anything
+        assert!(game.is_eliminated(0));
+        assert_eq!(
+            game.start_events()[..3],
+            [Event::PlayerTurnStarted(0), Event::Starved(1), Event::PlayerTurnStarted(1)]
+        );
+        assert_eq!(game.current_player(), 1);
+        assert_eq!(game.outcome(0), None);
+
+        // Carol starves at the start of the first turn
+        let events = game.apply(GameAction::Harvest).unwrap();
+        assert!(events.contains(&Event::Starved(1)));
+        assert!(game.is_eliminated(2));
+        assert_eq!(game.current_player(), 1);
+        assert_eq!(game.state().current_turn, 1);
+
+        let events = game.apply(GameAction::Harvest).unwrap();
+        assert!(!events.contains(&Event::PlayerTurnStarted(0)));
+        assert_eq!(game.current_player(), 1);
+        assert_eq!(game.state().current_turn, 2);
+    }
+}
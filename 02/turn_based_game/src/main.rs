@@ -1,191 +1,433 @@
+use std::env;
+use std::fs::File;
 use std::io;
+use std::process;
+use std::time::{SystemTime, UNIX_EPOCH};
 
-enum GameAction {
-    Build(BuildingType),
-    Harvest,
-    Mine(ResourceType),
-}
+use turn_based_game::map::{self, Position};
+use turn_based_game::market::Commodity;
+use turn_based_game::replay::{ActionLog, Step};
+use turn_based_game::ruleset::Ruleset;
+use turn_based_game::score::{HighScore, HighScores, Score};
+use turn_based_game::strategy::{self, Strategy, STRATEGIES};
+use turn_based_game::{save, Event, Game, GameAction, Outcome, PlayerState, ResourceType, RuleError};
 
-enum ResourceType {
-    Gold(u32),
-    Wood(u32),
-}
+const DEFAULT_RULES_FILE: &str = "rules.json";
+const DEFAULT_SAVE_FILE: &str = "savegame.json";
+const HIGH_SCORES_FILE: &str = "highscores.json";
+const USAGE: &str = "Usage: turn_based_game [<rules file>] [--log <log file>]";
 
-enum BuildingType {
-    Farm,
+/// Terminal frontend of the game, all input and output happens here.
+struct Terminal {
+    game: Game,
+    /// Strategy of every computer player, `None` for human players.
+    strategies: Vec<Option<Box<dyn Strategy>>>,
+    /// Every action is appended here, see the `replay` binary.
+    log: Option<ActionLog<File>>,
 }
 
-const FARM_GOLD_PRICE: u32 = 1000;
-const FARM_WOOD_PRICE: u32 = 500;
-
-const FARM_PRODUCTION: u32 = 100;
+impl Terminal {
+    fn create(game: Game, log: Option<ActionLog<File>>) -> Terminal {
+        let mut terminal = Terminal {
+            game,
+            strategies: Vec::new(),
+            log,
+        };
+        terminal.create_strategies();
+        terminal.log_start();
+        terminal
+    }
 
-struct Game {
-    current_turn: u32,
-    end_turn: u32,
-    resources: Resources,
-    buildings: Vec<BuildingType>,
-}
+    fn create_strategies(&mut self) {
+        let seed = self.game.state().seed;
+        self.strategies = self
+            .game
+            .state()
+            .players
+            .iter()
+            .enumerate()
+            .map(|(index, player)| {
+                let name = player.strategy.as_ref()?;
+                strategy::by_name(name, seed.wrapping_add(index as u64))
+            })
+            .collect();
+    }
 
-#[derive(Debug)]
-struct Resources {
-    gold: u32,
-    wood: u32,
-    food: u32,
-}
+    /// Applies the action and appends it to the log.
+    fn apply(&mut self, action: GameAction) -> Result<Vec<Event>, RuleError> {
+        let step = Step::apply(&mut self.game, action)?;
+        if let Some(log) = &mut self.log {
+            if let Err(error) = log.record(&step) {
+                println!("Cannot write action log, logging stopped: {}", error);
+                self.log = None;
+            }
+        }
+        Ok(step.events)
+    }
 
-impl Game {
-    fn new(end_turn: u32) -> Game {
-        Game {
-            current_turn: 0,
-            end_turn,
-            resources: Resources {
-                gold: 0,
-                wood: 0,
-                food: 0,
-            },
-            buildings: Vec::new(),
+    /// Logs the state the next actions start from.
+    fn log_start(&mut self) {
+        if let Some(log) = &mut self.log {
+            if let Err(error) = log.start(&self.game) {
+                println!("Cannot write action log, logging stopped: {}", error);
+                self.log = None;
+            }
         }
     }
 
+    fn is_multiplayer(&self) -> bool {
+        self.game.state().players.len() > 1
+    }
+
     fn game_loop(&mut self) {
-        while self.current_turn < self.end_turn {
-            println!("\nTurn {} started.", self.current_turn + 1);
-            self.do_start_of_turn_actions();
-            self.print_resources();
+        println!("\nTurn {} started.", self.game.state().current_turn + 1);
+        self.print_events(self.game.start_events());
+        while !self.game.is_over() {
+            let current = self.game.current_player();
+            let action = match &mut self.strategies[current] {
+                Some(strategy) => strategy.choose_action(&self.game),
+                None => {
+                    self.print_resources();
+                    self.get_game_action()
+                }
+            };
+            match self.apply(action) {
+                Ok(events) => self.print_events(&events),
+                Err(error) => {
+                    println!("{}", error);
+                    // computer player must not get stuck on an invalid action
+                    if self.strategies[current].is_some() {
+                        let events = self.apply(GameAction::EndTurn).expect("turn can always be ended");
+                        self.print_events(&events);
+                    }
+                }
+            }
+        }
+        self.print_scores();
+    }
 
-            let action = Game::get_game_action();
-            self.do_action(action);
+    fn print_scores(&self) {
+        let mut high_scores = HighScores::load(HIGH_SCORES_FILE).unwrap_or_else(|error| {
+            println!("Cannot read high scores: {}", error);
+            HighScores::default()
+        });
+        for (index, player) in self.game.state().players.iter().enumerate() {
+            let outcome = self.game.outcome(index).expect("game is over");
+            let score = Score::of(&self.game, index);
+            println!("\n{}: {}\nScore:\n{}", player.name, outcome, score);
 
-            self.current_turn += 1;
+            let entry = HighScore {
+                name: player.name.clone(),
+                score: score.total(),
+                won: matches!(outcome, Outcome::Won(_)),
+                turns: self.game.state().current_turn,
+            };
+            match high_scores.add(entry) {
+                Some(position) => println!("New high score at position {}!", position + 1),
+                None => println!("Not enough for the high scores."),
+            }
+        }
+        if let Err(error) = high_scores.save(HIGH_SCORES_FILE) {
+            println!("Cannot save high scores: {}", error);
         }
-        println!("\nGame ended.");
+        print!("\nHigh scores:\n{}", high_scores);
     }
 
-    fn do_start_of_turn_actions(&mut self) {
-        for building in &self.buildings {
-            match building {
-                BuildingType::Farm => {
-                    self.resources.food += FARM_PRODUCTION;
-                }
+    fn print_events(&self, events: &[Event]) {
+        for event in events {
+            match event {
+                Event::TurnStarted(_) | Event::GameEnded => println!("\n{}", event),
+                Event::PlayerTurnStarted(player) => self.print_player_on_turn(*player),
+                _ => println!("{}", event),
             }
         }
     }
 
-    fn do_action(&mut self, action: GameAction) {
-        match action {
-            GameAction::Build(building_type) => self.do_build_action(building_type),
-            GameAction::Harvest => println!("Harvesting..."),
-            GameAction::Mine(resource_type) => self.do_mine_action(resource_type),
+    fn print_player_on_turn(&self, index: usize) {
+        if self.is_multiplayer() {
+            println!("{} is on turn.", self.game.player(index).name);
         }
     }
 
-    fn do_build_action(&mut self, building_type: BuildingType) {
-        use BuildingType::*;
-        match building_type {
-            Farm => {
-                if self.resources.gold >= FARM_GOLD_PRICE && self.resources.wood >= FARM_WOOD_PRICE
-                {
-                    self.resources.gold -= FARM_GOLD_PRICE;
-                    self.resources.wood -= FARM_WOOD_PRICE;
-                    println!("Building farm...");
-                    self.buildings.push(building_type);
-                } else {
-                    println!("Not enough resources!");
+    fn print_resources(&self) {
+        let current = self.game.current_player();
+        let player = self.game.player(current);
+        let bonus = self.game.mining_bonus(current);
+        println!(
+            "Your resources:\n\
+                  {:?}\n\
+                  Population: {}, crops in the fields: {}\n\
+                  Mining bonus: {} % gold, {} % wood",
+            player.resources, player.population, player.crops, bonus.gold, bonus.wood
+        );
+        let prices = self.game.state().market.prices;
+        println!(
+            "Market prices for 100 units: gold {}, wood {}, food {}",
+            prices.gold, prices.wood, prices.food
+        );
+        if let Some(research) = &player.research {
+            let turns = self.game.ruleset().technology(&research.technology).map_or(0, |technology| technology.turns);
+            println!("Researching {}: {} of {} turns done", research.technology, research.turns_done, turns);
+        }
+        println!(
+            "{}\n\
+             @ home, upper case your buildings, lower case the others, \
+             . plains, f forest, h hills, ? unexplored",
+            map::render(self.game.state(), current)
+        );
+    }
+
+    fn save_game(&self) {
+        let file = Terminal::get_file_name();
+        match save::save(self.game.state(), &file) {
+            Ok(()) => println!("Game saved to {}.", file),
+            Err(error) => println!("{}", error),
+        }
+    }
+
+    fn load_game(&mut self) {
+        let file = Terminal::get_file_name();
+        match save::load(&file, self.game.ruleset()) {
+            Ok(state) => {
+                self.game = Game::from_state(self.game.ruleset().clone(), state);
+                self.create_strategies();
+                self.log_start();
+                println!("Game loaded from {}, turn {}.", file, self.game.state().current_turn + 1);
+                self.print_player_on_turn(self.game.current_player());
+                if self.strategies[self.game.current_player()].is_none() {
+                    self.print_resources();
                 }
             }
-        };
-        self.print_resources();
+            Err(error) => println!("{}", error),
+        }
     }
 
-    fn do_mine_action(&mut self, resource_type: ResourceType) {
-        use ResourceType::*;
-        match resource_type {
-            Gold(amount) => {
-                println!("Mining {} gold...", amount);
-                self.resources.gold += amount;
+    /// Saving and loading cost no action points, the player is asked again for an action.
+    fn get_game_action(&mut self) -> GameAction {
+        loop {
+            let costs = &self.game.ruleset().actions.costs;
+            let menu = format!(
+                "Choose your action ({} action points left):\n\
+                  1. Build ({} points)\n\
+                  2. Harvest ({} points)\n\
+                  3. Mine ({} points)\n\
+                  4. Explore ({} points)\n\
+                  5. Research ({} points)\n\
+                  6. Trade ({} points)\n\
+                  7. End turn\n\
+                  8. Save game\n\
+                  9. Load game",
+                self.game.player(self.game.current_player()).action_points,
+                costs.build,
+                costs.harvest,
+                costs.mine,
+                costs.explore,
+                costs.research,
+                costs.trade
+            );
+            match choose(&menu, 9) {
+                0 => {
+                    let sites = self.game.building_sites(self.game.current_player());
+                    if sites.is_empty() {
+                        println!("There is no free explored tile next to your territory, explore first!");
+                        continue;
+                    }
+                    let building = self.get_building_type();
+                    return GameAction::Build(building, Terminal::get_position(&sites));
+                }
+                1 => return GameAction::Harvest,
+                2 => return GameAction::Mine(Terminal::get_resource_type()),
+                3 => return GameAction::Explore,
+                4 => {
+                    if let Some(research) = &self.game.player(self.game.current_player()).research {
+                        println!("You are still researching {}!", research.technology);
+                        continue;
+                    }
+                    match self.get_technology() {
+                        Some(technology) => return GameAction::Research(technology),
+                        None => println!("There is nothing left to research!"),
+                    }
+                }
+                5 => return self.get_trade(),
+                6 => return GameAction::EndTurn,
+                7 => self.save_game(),
+                _ => self.load_game(),
             }
-            Wood(amount) => {
-                println!("Mining {} wood...", amount);
-                self.resources.wood += amount;
+        }
+    }
+
+    fn get_building_type(&self) -> String {
+        let buildings = &self.game.ruleset().buildings;
+        let current = self.game.current_player();
+        let mut menu = "What do you want to build:".to_string();
+        for (index, building) in buildings.iter().enumerate() {
+            match self.game.ruleset().unlocked_by(&building.name).next() {
+                Some(technology) if !self.game.is_unlocked(current, &building.name) => {
+                    menu += &format!("\n{}. {} (needs {})", index + 1, building.name, technology.name)
+                }
+                _ => menu += &format!("\n{}. {} (costs {:?})", index + 1, building.name, building.cost),
             }
-        };
-        self.print_resources();
+        }
+        buildings[choose(&menu, buildings.len())].name.clone()
     }
 
-    fn print_resources(&mut self) {
-        println!(
-            "Your resources:\n\
-                  {:?}",
-            self.resources
+    /// Technology to research, `None` when there is nothing the player could research.
+    fn get_technology(&self) -> Option<String> {
+        let technologies = self.game.available_technologies(self.game.current_player());
+        if technologies.is_empty() {
+            return None;
+        }
+        let mut menu = "What do you want to research:".to_string();
+        for (index, technology) in technologies.iter().enumerate() {
+            menu += &format!(
+                "\n{}. {} ({} turns, costs {:?} per turn)",
+                index + 1,
+                technology.name,
+                technology.turns,
+                technology.cost_per_turn
+            );
+        }
+        Some(technologies[choose(&menu, technologies.len())].name.clone())
+    }
+
+    fn get_trade(&self) -> GameAction {
+        let sold = Terminal::get_commodity("What do you want to sell:");
+        let bought = Terminal::get_commodity("What do you want to buy:");
+        let stock = sold.amount(&self.game.player(self.game.current_player()).resources);
+        let question = format!("How much {} do you want to sell (you have {}):", sold, stock);
+        let amount = ask(&question, |answer| answer.parse::<u32>().ok());
+        let received = self.game.state().market.quote(sold, amount, bought);
+        println!("You will get {} {} for {} {}.", received, bought, amount, sold);
+        GameAction::Trade(sold, amount, bought)
+    }
+
+    fn get_commodity(question: &str) -> Commodity {
+        let menu = format!(
+            "{}\n\
+              1. Gold\n\
+              2. Wood\n\
+              3. Food",
+            question
         );
+        Commodity::ALL[choose(&menu, Commodity::ALL.len())]
     }
-    fn get_game_action() -> GameAction {
-        loop {
-            let mut action = String::new();
-            println!(
-                "Choose your action:\n\
-                  1. Build\n\
-                  2. Harvest\n\
-                  3. Mine"
+
+    fn get_position(sites: &[Position]) -> Position {
+        let list: Vec<_> = sites.iter().map(|(x, y)| format!("{},{}", x, y)).collect();
+        let question = format!("Where do you want to build (free tiles: {}):", list.join(" "));
+        ask(&question, |answer| {
+            let coordinates: Vec<_> = answer.split(',').map(|number| number.trim().parse()).collect();
+            match coordinates.as_slice() {
+                [Ok(x), Ok(y)] if sites.contains(&(*x, *y)) => Some((*x, *y)),
+                _ => None,
+            }
+        })
+    }
+
+    /// Asks for the players until an empty line or until the map is full,
+    /// names of strategies add computer players.
+    fn get_players(ruleset: &Ruleset) -> Vec<PlayerState> {
+        let mut players = Vec::new();
+        while players.len() < ruleset.max_players() {
+            let question = format!(
+                "Enter name of player {}, one of {:?} for a computer player or nothing to start the game:",
+                players.len() + 1,
+                STRATEGIES
             );
-            io::stdin().read_line(&mut action).unwrap();
-
-            match action.trim().parse::<u32>() {
-                Ok(entered_number) => match entered_number {
-                    1 => return GameAction::Build(Game::get_building_type()),
-                    2 => return GameAction::Harvest,
-                    3 => return GameAction::Mine(Game::get_resource_type()),
-                    _ => continue,
-                },
-                Err(_) => continue,
-            };
+            match ask(&question, |name| Some(name.to_string())).as_str() {
+                "" if players.is_empty() => return vec![PlayerState::new("Anonymous", ruleset)],
+                "" => return players,
+                name if STRATEGIES.contains(&name) => {
+                    let mut player = PlayerState::new(&format!("Computer {} ({})", players.len() + 1, name), ruleset);
+                    player.strategy = Some(name.to_string());
+                    players.push(player);
+                }
+                name => players.push(PlayerState::new(name, ruleset)),
+            }
         }
+        println!("There is no room for more players on the map.");
+        players
     }
 
-    fn get_building_type() -> BuildingType {
-        loop {
-            let mut action = String::new();
-            println!(
-                "What do you want to build:\n\
-                  1. Farm"
-            );
-            io::stdin().read_line(&mut action).unwrap();
-
-            match action.trim().parse::<u32>() {
-                Ok(entered_number) => match entered_number {
-                    1 => return BuildingType::Farm,
-                    _ => continue,
-                },
-                Err(_) => continue,
-            };
+    fn get_file_name() -> String {
+        let question = format!("Enter file name (default {}):", DEFAULT_SAVE_FILE);
+        match ask(&question, |file| Some(file.to_string())).as_str() {
+            "" => DEFAULT_SAVE_FILE.to_string(),
+            file => file.to_string(),
         }
     }
 
     fn get_resource_type() -> ResourceType {
-        loop {
-            let mut action = String::new();
-            println!(
-                "What do you want to mine:\n\
-                  1. Gold\n\
-                  2. Wood"
-            );
-            io::stdin().read_line(&mut action).unwrap();
-
-            use ResourceType::*;
-            match action.trim().parse::<u32>() {
-                Ok(entered_number) => match entered_number {
-                    1 => return Gold(500),
-                    2 => return Wood(500),
-                    _ => continue,
-                },
-                Err(_) => continue,
-            };
+        let menu = "What do you want to mine:\n\
+                      1. Gold\n\
+                      2. Wood";
+        [ResourceType::Gold, ResourceType::Wood][choose(menu, 2)]
+    }
+}
+
+/// Asks the question until the parser accepts the trimmed answer. The game ends when the input
+/// ends or cannot be read, because the question would be asked forever.
+fn ask<T>(question: &str, parse: impl Fn(&str) -> Option<T>) -> T {
+    loop {
+        println!("{}", question);
+        let mut answer = String::new();
+        match io::stdin().read_line(&mut answer) {
+            Ok(0) => {
+                eprintln!("Input ended, quitting the game.");
+                process::exit(1);
+            }
+            Err(error) => {
+                eprintln!("Cannot read input, quitting the game: {}", error);
+                process::exit(1);
+            }
+            Ok(_) => {}
+        }
+        if let Some(value) = parse(answer.trim()) {
+            return value;
         }
     }
 }
 
+/// Asks for one of the numbered options of the menu, returns its index counted from 0.
+fn choose(menu: &str, options: usize) -> usize {
+    ask(menu, |answer| match answer.parse::<usize>() {
+        Ok(number) if number >= 1 && number <= options => Some(number - 1),
+        _ => None,
+    })
+}
+
+fn usage_error(message: &str) -> ! {
+    eprintln!("{}\n{}", message, USAGE);
+    process::exit(2);
+}
+
 fn main() {
-    let mut game = Game::new(10);
-    game.game_loop();
+    let mut rules_file = None;
+    let mut log_file = None;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--log" => match args.next() {
+                Some(file) => log_file = Some(file),
+                None => usage_error("Missing log file"),
+            },
+            _ if rules_file.is_none() && !arg.starts_with("--") => rules_file = Some(arg),
+            _ => usage_error(&format!("Unexpected argument '{}'", arg)),
+        }
+    }
+    let rules_file = rules_file.unwrap_or_else(|| DEFAULT_RULES_FILE.to_string());
+    let ruleset = Ruleset::load(&rules_file).unwrap_or_else(|error| {
+        eprintln!("{}", error);
+        process::exit(1);
+    });
+    let log = log_file.map(|file| {
+        ActionLog::open(&file).unwrap_or_else(|error| {
+            eprintln!("Cannot open {}: {}", file, error);
+            process::exit(1);
+        })
+    });
+    let players = Terminal::get_players(&ruleset);
+    let seed = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |time| time.as_secs());
+    let mut terminal = Terminal::create(Game::with_players(ruleset, players, 10).with_seed(seed), log);
+    terminal.game_loop();
 }
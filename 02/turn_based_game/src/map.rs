@@ -0,0 +1,220 @@
+use std::collections::BTreeSet;
+use std::fmt;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use crate::ruleset::MapRules;
+use crate::GameState;
+
+/// Column and row of a tile, counted from 0 from the top left corner.
+pub type Position = (usize, usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Terrain {
+    Plains,
+    Forest,
+    Hills,
+}
+
+impl Terrain {
+    /// Character used by the [text renderer](fn.render.html).
+    pub fn symbol(self) -> char {
+        match self {
+            Terrain::Plains => '.',
+            Terrain::Forest => 'f',
+            Terrain::Hills => 'h',
+        }
+    }
+}
+
+impl fmt::Display for Terrain {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Terrain::Plains => write!(f, "plains"),
+            Terrain::Forest => write!(f, "forest"),
+            Terrain::Hills => write!(f, "hills"),
+        }
+    }
+}
+
+/// Building of a player standing on a tile of the map.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Building {
+    pub name: String,
+    pub position: Position,
+}
+
+/// # Map
+/// Grid of tiles shared by all players.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Map {
+    pub width: usize,
+    pub height: usize,
+    /// Terrain of the tiles, row by row.
+    pub tiles: Vec<Terrain>,
+}
+
+impl Map {
+    /// Generates random terrain, the same rules and seed always give the same map.
+    /// Homes of the players are always on plains.
+    pub fn generate(rules: &MapRules, seed: u64, players: usize) -> Map {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let total_weight: u32 = rules.terrain.iter().map(|terrain| terrain.weight).sum();
+        let tiles = (0..rules.width * rules.height)
+            .map(|_| {
+                let mut roll = rng.gen_range(0, total_weight);
+                for terrain in &rules.terrain {
+                    if roll < terrain.weight {
+                        return terrain.terrain;
+                    }
+                    roll -= terrain.weight;
+                }
+                unreachable!("roll is below the total weight")
+            })
+            .collect();
+        let mut map = Map {
+            width: rules.width,
+            height: rules.height,
+            tiles,
+        };
+        for index in 0..players {
+            let home = map.home(index, players);
+            map.tiles[home.1 * map.width + home.0] = Terrain::Plains;
+        }
+        map
+    }
+
+    pub fn terrain(&self, (x, y): Position) -> Option<Terrain> {
+        if x < self.width && y < self.height {
+            Some(self.tiles[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    /// Tiles at most the given number of steps away, including diagonal steps.
+    pub fn around(&self, (x, y): Position, distance: usize) -> Vec<Position> {
+        let columns = x.saturating_sub(distance)..=(x + distance).min(self.width - 1);
+        let rows = y.saturating_sub(distance)..=(y + distance).min(self.height - 1);
+        rows.flat_map(|row| columns.clone().map(move |column| (column, row)))
+            .collect()
+    }
+
+    /// Home of the player with the given index. Homes are spread over the top and the bottom row
+    /// next to the edge. Every player has a different home when the map is at least four tiles high
+    /// and has at least as many columns as there are players.
+    pub fn home(&self, index: usize, players: usize) -> Position {
+        let x = (2 * index + 1) * self.width / (2 * players);
+        let y = match index % 2 {
+            0 => 1,
+            _ => self.height - 2,
+        };
+        (x, y)
+    }
+}
+
+/// Draws the map as seen by the player: unexplored tiles are `?`, homes `@` and buildings
+/// the first letter of their name, in upper case for the player's own ones and lower case for the others.
+pub fn render(state: &GameState, player: usize) -> String {
+    let map = &state.map;
+    let explored: &BTreeSet<Position> = &state.players[player].explored;
+    let mut lines = Vec::new();
+    lines.push(format!("  {}", (0..map.width).map(|x| format!("{:>3}", x)).collect::<String>()));
+    for y in 0..map.height {
+        let mut line = format!("{:>2}", y);
+        for x in 0..map.width {
+            let symbol = if !explored.contains(&(x, y)) {
+                '?'
+            } else {
+                symbol_at(state, player, (x, y))
+            };
+            line.push_str("  ");
+            line.push(symbol);
+        }
+        lines.push(line);
+    }
+    lines.join("\n")
+}
+
+fn symbol_at(state: &GameState, player: usize, position: Position) -> char {
+    for (index, other) in state.players.iter().enumerate() {
+        if other.home == position {
+            return '@';
+        }
+        if let Some(building) = other.buildings.iter().find(|building| building.position == position) {
+            let letter = building.name.chars().next().unwrap_or('#');
+            return if index == player {
+                letter.to_ascii_uppercase()
+            } else {
+                letter.to_ascii_lowercase()
+            };
+        }
+    }
+    state.map.terrain(position).map_or(' ', Terrain::symbol)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ruleset::{Ruleset, TerrainRules};
+    use crate::Game;
+
+    fn rules() -> MapRules {
+        let terrain = |terrain, weight| TerrainRules {
+            terrain,
+            weight,
+            mining_bonus: Default::default(),
+        };
+        MapRules {
+            width: 8,
+            height: 5,
+            terrain: vec![terrain(Terrain::Forest, 1), terrain(Terrain::Hills, 1)],
+            ..MapRules::default()
+        }
+    }
+
+    #[test]
+    fn generated_map_depends_only_on_seed() {
+        let map = Map::generate(&rules(), 1, 2);
+        assert_eq!(map.tiles.len(), 40);
+        assert_eq!(map, Map::generate(&rules(), 1, 2));
+        assert_ne!(map, Map::generate(&rules(), 2, 2));
+    }
+
+    #[test]
+    fn homes_are_on_plains() {
+        let map = Map::generate(&rules(), 1, 2);
+        assert_eq!(map.home(0, 2), (2, 1));
+        assert_eq!(map.home(1, 2), (6, 3));
+        assert_eq!(map.terrain((2, 1)), Some(Terrain::Plains));
+        assert_eq!(map.terrain((6, 3)), Some(Terrain::Plains));
+        assert_eq!(map.terrain((8, 0)), None);
+    }
+
+    #[test]
+    fn render_shows_only_explored_tiles() {
+        let ruleset = Ruleset::from_json(r#"{"buildings": [{"name": "Farm"}], "map": {"width": 4, "height": 4}}"#).unwrap();
+        let mut state = Game::new(ruleset, 10).state().clone();
+        state.players[0].buildings.push(Building {
+            name: "Farm".to_string(),
+            position: (2, 0),
+        });
+        let expected = "    0  1  2  3\n \
+                        0  ?  .  F  .\n \
+                        1  ?  .  @  .\n \
+                        2  ?  .  .  .\n \
+                        3  ?  ?  ?  ?";
+        assert_eq!(render(&state, 0), expected);
+    }
+
+    #[test]
+    fn around_stays_inside_map() {
+        let map = Map::generate(&rules(), 1, 1);
+        assert_eq!(map.around((0, 0), 1), vec![(0, 0), (1, 0), (0, 1), (1, 1)]);
+        assert_eq!(map.around((3, 2), 1).len(), 9);
+        assert_eq!(map.around((7, 4), 2).len(), 9);
+    }
+}
@@ -0,0 +1,143 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::resources::Resources;
+use crate::ruleset::MarketRules;
+
+/// Resource which can be traded at the [market](struct.Market.html).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Commodity {
+    Gold,
+    Wood,
+    Food,
+}
+
+impl Commodity {
+    pub const ALL: [Commodity; 3] = [Commodity::Gold, Commodity::Wood, Commodity::Food];
+
+    /// Amount of the commodity in the resources.
+    pub fn amount(self, resources: &Resources) -> u32 {
+        match self {
+            Commodity::Gold => resources.gold,
+            Commodity::Wood => resources.wood,
+            Commodity::Food => resources.food,
+        }
+    }
+
+    pub fn amount_mut(self, resources: &mut Resources) -> &mut u32 {
+        match self {
+            Commodity::Gold => &mut resources.gold,
+            Commodity::Wood => &mut resources.wood,
+            Commodity::Food => &mut resources.food,
+        }
+    }
+}
+
+impl fmt::Display for Commodity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Commodity::Gold => write!(f, "gold"),
+            Commodity::Wood => write!(f, "wood"),
+            Commodity::Food => write!(f, "food"),
+        }
+    }
+}
+
+/// # Market
+/// Prices shared by all players. Selling makes a commodity cheaper and buying makes it
+/// more expensive, every turn the prices return part of the way to the base prices.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Market {
+    /// Value of 100 units of every commodity.
+    pub prices: Resources,
+}
+
+impl Market {
+    /// Market with the base prices.
+    pub fn new(rules: &MarketRules) -> Market {
+        Market {
+            prices: rules.base_prices,
+        }
+    }
+
+    pub fn price(&self, commodity: Commodity) -> u32 {
+        commodity.amount(&self.prices)
+    }
+
+    /// Amount of the bought commodity which is paid for the sold one at the current prices.
+    pub fn quote(&self, sold: Commodity, amount: u32, bought: Commodity) -> u32 {
+        let value = u64::from(amount) * u64::from(self.price(sold));
+        (value / u64::from(self.price(bought).max(1))).min(u64::from(u32::MAX)) as u32
+    }
+
+    /// Moves the prices after the trade, the more is traded the more they move.
+    pub fn trade(&mut self, rules: &MarketRules, sold: (Commodity, u32), bought: (Commodity, u32)) {
+        let change = |amount: u32| (u64::from(amount) * u64::from(rules.impact_percent) / 100).min(90);
+        let (commodity, amount) = sold;
+        let price = commodity.amount_mut(&mut self.prices);
+        *price = (u64::from(*price) * (100 - change(amount)) / 100).max(1) as u32;
+        let (commodity, amount) = bought;
+        let price = commodity.amount_mut(&mut self.prices);
+        *price = (u64::from(*price) * (100 + change(amount)) / 100).min(u64::from(u32::MAX)) as u32;
+    }
+
+    /// Moves every price the recovery percent of the way back to its base price,
+    /// at least by one until it is reached.
+    pub fn recover(&mut self, rules: &MarketRules) {
+        for &commodity in Commodity::ALL.iter() {
+            let base = i64::from(commodity.amount(&rules.base_prices));
+            let price = commodity.amount_mut(&mut self.prices);
+            let difference = base - i64::from(*price);
+            let mut step = difference * i64::from(rules.recovery_percent) / 100;
+            if step == 0 && rules.recovery_percent > 0 {
+                step = difference.signum();
+            }
+            *price = (i64::from(*price) + step) as u32;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules() -> MarketRules {
+        MarketRules {
+            base_prices: Resources { gold: 100, wood: 50, food: 20 },
+            impact_percent: 10,
+            recovery_percent: 50,
+        }
+    }
+
+    #[test]
+    fn quote_uses_price_ratio() {
+        let market = Market::new(&rules());
+        assert_eq!(market.quote(Commodity::Gold, 100, Commodity::Wood), 200);
+        assert_eq!(market.quote(Commodity::Food, 100, Commodity::Gold), 20);
+        assert_eq!(market.quote(Commodity::Food, 4, Commodity::Gold), 0);
+    }
+
+    #[test]
+    fn trading_moves_prices() {
+        let mut market = Market::new(&rules());
+        market.trade(&rules(), (Commodity::Gold, 100), (Commodity::Wood, 200));
+        assert_eq!(market.prices, Resources { gold: 90, wood: 60, food: 20 });
+        // price changes are limited, nothing becomes free
+        market.trade(&rules(), (Commodity::Gold, 100_000), (Commodity::Food, 1));
+        assert_eq!(market.price(Commodity::Gold), 9);
+    }
+
+    #[test]
+    fn prices_recover_to_base() {
+        let mut market = Market::new(&rules());
+        market.prices = Resources { gold: 60, wood: 51, food: 30 };
+        market.recover(&rules());
+        assert_eq!(market.prices, Resources { gold: 80, wood: 50, food: 25 });
+        for _ in 0..10 {
+            market.recover(&rules());
+        }
+        assert_eq!(market.prices, rules().base_prices);
+    }
+}
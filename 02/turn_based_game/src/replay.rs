@@ -0,0 +1,339 @@
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::resources::Resources;
+use crate::ruleset::Ruleset;
+use crate::save::{self, SaveError, SAVE_VERSION};
+use crate::{Event, Game, GameAction, GameState, RuleError};
+
+/// # LogEntry
+/// One line of an action log, the log is a JSON object per line.
+/// The state is read as plain JSON, so that it can be migrated when the log is older.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum LogEntry<S = GameState> {
+    /// Game started or a saved game was loaded, the following steps continue from this state.
+    /// The state contains the seed of all random numbers of the game.
+    Start {
+        version: u32,
+        ruleset: Box<Ruleset>,
+        state: S,
+        /// Events of the start of the first turn, which are already part of the state.
+        #[serde(default)]
+        events: Vec<Event>,
+    },
+    Step(Step),
+}
+
+/// Accepted action with everything it caused.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Step {
+    /// Number of finished turns before the action.
+    pub turn: u32,
+    /// Index of the player who did the action.
+    pub player: usize,
+    pub action: GameAction,
+    /// Events of the action, including the start of the turn of the next player.
+    pub events: Vec<Event>,
+    /// Resources of every player after the action.
+    pub resources: Vec<Resources>,
+}
+
+impl Step {
+    /// Applies the action to the game and records the step, rejected actions are not recorded.
+    pub fn apply(game: &mut Game, action: GameAction) -> Result<Step, RuleError> {
+        let turn = game.state().current_turn;
+        let player = game.current_player();
+        let events = game.apply(action.clone())?;
+        Ok(Step {
+            turn,
+            player,
+            action,
+            events,
+            resources: game.state().players.iter().map(|player| player.resources).collect(),
+        })
+    }
+}
+
+/// # ActionLog
+/// Append-only log of a game, which can be [replayed](fn.replay.html) to reproduce it.
+pub struct ActionLog<W: Write> {
+    output: W,
+}
+
+impl ActionLog<File> {
+    /// Appends to the log file, it is created when it does not exist.
+    pub fn open(log_file: &str) -> Result<ActionLog<File>, io::Error> {
+        let output = OpenOptions::new().create(true).append(true).open(log_file)?;
+        Ok(ActionLog::new(output))
+    }
+}
+
+impl<W: Write> ActionLog<W> {
+    pub fn new(output: W) -> ActionLog<W> {
+        ActionLog { output }
+    }
+
+    /// Records the state the next steps start from, when a game is started or loaded.
+    pub fn start(&mut self, game: &Game) -> Result<(), io::Error> {
+        self.write(&LogEntry::Start {
+            version: SAVE_VERSION,
+            ruleset: Box::new(game.ruleset().clone()),
+            state: game.state().clone(),
+            events: game.start_events().to_vec(),
+        })
+    }
+
+    pub fn record(&mut self, step: &Step) -> Result<(), io::Error> {
+        self.write(&LogEntry::Step(step.clone()))
+    }
+
+    /// Every entry is flushed, so that the log is complete even when the game crashes.
+    fn write(&mut self, entry: &LogEntry) -> Result<(), io::Error> {
+        serde_json::to_writer(&mut self.output, entry)?;
+        writeln!(self.output)?;
+        self.output.flush()
+    }
+
+    pub fn into_inner(self) -> W {
+        self.output
+    }
+}
+
+#[derive(Debug)]
+pub enum ReplayError {
+    Io(io::Error),
+    /// Line with the given number, counted from 1, is not a log entry.
+    Parse(usize, serde_json::Error),
+    /// Log was written by a newer version of the game.
+    UnsupportedVersion(u32),
+    /// Log is well-formed but cannot be replayed.
+    Invalid(String),
+    /// Action on the given line was rejected.
+    Rejected(usize, RuleError),
+    /// Action on the given line led somewhere else than when it was recorded.
+    Diverged { line: usize, expected: Box<Step>, actual: Box<Step> },
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReplayError::Io(error) => write!(f, "Cannot read log: {}", error),
+            ReplayError::Parse(line, error) => write!(f, "Log is corrupted on line {}: {}", line, error),
+            ReplayError::UnsupportedVersion(version) => {
+                write!(f, "Log version {} is not supported, the newest one is {}", version, SAVE_VERSION)
+            }
+            ReplayError::Invalid(message) => write!(f, "Log is invalid: {}", message),
+            ReplayError::Rejected(line, error) => write!(f, "Action on line {} was rejected: {}", line, error),
+            ReplayError::Diverged { line, expected, actual } => write!(
+                f,
+                "Replay diverged on line {}:\nrecorded: {:?}\n{:?}\nreplayed: {:?}\n{:?}",
+                line, expected.events, expected.resources, actual.events, actual.resources
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+impl From<io::Error> for ReplayError {
+    fn from(error: io::Error) -> Self {
+        ReplayError::Io(error)
+    }
+}
+
+impl ReplayError {
+    /// Error of the state the game starts from on the given line.
+    fn from_start(line: usize, error: SaveError) -> Self {
+        match error {
+            SaveError::Io(error) => ReplayError::Io(error),
+            SaveError::Parse(error) => ReplayError::Parse(line, error),
+            SaveError::UnsupportedVersion(version) => ReplayError::UnsupportedVersion(version),
+            SaveError::UnknownBuilding(name) => ReplayError::Invalid(format!("Unknown building '{}'", name)),
+            SaveError::Invalid(message) => ReplayError::Invalid(message),
+        }
+    }
+}
+
+/// Replays the log file, see [`replay`](fn.replay.html).
+pub fn replay_file(log_file: &str, ruleset: Option<&Ruleset>) -> Result<Game, ReplayError> {
+    replay(BufReader::new(File::open(log_file)?), ruleset)
+}
+
+/// Does all actions of the log again and checks that every one of them has the same events
+/// and leaves the players with the same resources as when it was recorded.
+/// The ruleset in the log is used unless another one is given, to check how a change
+/// of the rules affects recorded games. Logs of older versions are migrated like saved games.
+/// Returns the game after the last action.
+pub fn replay<R: BufRead>(input: R, ruleset: Option<&Ruleset>) -> Result<Game, ReplayError> {
+    let mut game = None;
+    for (index, line) in input.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let entry: LogEntry<Value> =
+            serde_json::from_str(&line).map_err(|error| ReplayError::Parse(line_number, error))?;
+        match entry {
+            LogEntry::Start {
+                version,
+                ruleset: recorded,
+                state,
+                ..
+            } => {
+                let ruleset = match ruleset {
+                    Some(ruleset) => ruleset.clone(),
+                    None => {
+                        // the recorded ruleset was not loaded through `Ruleset::from_json`
+                        recorded.validate().map_err(|error| ReplayError::Invalid(error.to_string()))?;
+                        *recorded
+                    }
+                };
+                let state = save::upgrade(version, state, &ruleset)
+                    .map_err(|error| ReplayError::from_start(line_number, error))?;
+                game = Some(Game::from_state(ruleset, state));
+            }
+            LogEntry::Step(expected) => {
+                let game = game
+                    .as_mut()
+                    .ok_or_else(|| ReplayError::Invalid("Action before the start of the game".to_string()))?;
+                let actual = Step::apply(game, expected.action.clone())
+                    .map_err(|error| ReplayError::Rejected(line_number, error))?;
+                if actual != expected {
+                    return Err(ReplayError::Diverged {
+                        line: line_number,
+                        expected: Box::new(expected),
+                        actual: Box::new(actual),
+                    });
+                }
+            }
+        }
+    }
+    game.ok_or_else(|| ReplayError::Invalid("Log is empty".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::{self, STRATEGIES};
+    use crate::PlayerState;
+
+    fn ruleset() -> Ruleset {
+        Ruleset::from_json(include_str!("../rules.json")).unwrap()
+    }
+
+    /// Log of a game between all strategies.
+    fn recorded_game(seed: u64) -> (Game, Vec<u8>) {
+        let ruleset = ruleset();
+        let players = STRATEGIES.iter().map(|name| PlayerState::new(name, &ruleset)).collect();
+        let mut game = Game::with_players(ruleset, players, 10).with_seed(seed);
+        let mut strategies: Vec<_> = STRATEGIES.iter().map(|name| strategy::by_name(name, seed).unwrap()).collect();
+        let mut log = ActionLog::new(Vec::new());
+        log.start(&game).unwrap();
+        while !game.is_over() {
+            let action = strategies[game.current_player()].choose_action(&game);
+            let step = Step::apply(&mut game, action).unwrap();
+            log.record(&step).unwrap();
+        }
+        (game, log.into_inner())
+    }
+
+    #[test]
+    fn replay_reproduces_game() {
+        let (game, log) = recorded_game(7);
+        let replayed = replay(log.as_slice(), None).unwrap();
+        assert_eq!(replayed.state(), game.state());
+    }
+
+    #[test]
+    fn start_events_are_logged() {
+        let (game, log) = recorded_game(7);
+        let start = String::from_utf8(log).unwrap().lines().next().unwrap().to_string();
+        match serde_json::from_str::<LogEntry>(&start).unwrap() {
+            LogEntry::Start { events, .. } => {
+                assert_eq!(events, game.start_events());
+                assert_eq!(events[0], Event::PlayerTurnStarted(0));
+            }
+            LogEntry::Step(step) => panic!("log starts with {:?}", step),
+        }
+    }
+
+    #[test]
+    fn changed_rules_are_detected() {
+        let (_, log) = recorded_game(7);
+        let mut ruleset = ruleset();
+        ruleset.mining.min_yield += 1;
+        match replay(log.as_slice(), Some(&ruleset)) {
+            Err(ReplayError::Diverged { line, expected, actual }) => {
+                assert!(line > 1);
+                assert_ne!(expected.resources, actual.resources);
+            }
+            other => panic!("replay did not diverge: {:?}", other.map(|game| game.state().clone())),
+        }
+    }
+
+    #[test]
+    fn edited_log_is_detected() {
+        let (_, log) = recorded_game(3);
+        let log = String::from_utf8(log).unwrap();
+        let mut lines: Vec<&str> = log.lines().collect();
+        // an action is lost, everything after it happens to the wrong player
+        lines.remove(1);
+        let result = replay(lines.join("\n").as_bytes(), None);
+        assert!(matches!(result, Err(ReplayError::Diverged { line: 2, .. })));
+    }
+
+    /// Log with the start entry changed by the function.
+    fn edit_start(log: &[u8], edit: impl FnOnce(&mut Value)) -> String {
+        let log = String::from_utf8(log.to_vec()).unwrap();
+        let mut lines: Vec<String> = log.lines().map(str::to_string).collect();
+        let mut start: Value = serde_json::from_str(&lines[0]).unwrap();
+        edit(&mut start);
+        lines[0] = start.to_string();
+        lines.join("\n")
+    }
+
+    #[test]
+    fn older_log_is_migrated() {
+        let (game, log) = recorded_game(5);
+        // version 7 had no action points
+        let log = edit_start(&log, |start| {
+            start["version"] = 7.into();
+            for player in start["state"]["players"].as_array_mut().unwrap() {
+                player.as_object_mut().unwrap().remove("action_points");
+            }
+        });
+        let replayed = replay(log.as_bytes(), None).unwrap();
+        assert_eq!(replayed.state(), game.state());
+    }
+
+    #[test]
+    fn invalid_start_is_rejected() {
+        let (_, log) = recorded_game(5);
+        let edited = edit_start(&log, |start| start["version"] = 999.into());
+        assert!(matches!(replay(edited.as_bytes(), None), Err(ReplayError::UnsupportedVersion(999))));
+        let edited = edit_start(&log, |start| start["state"]["current_player"] = 99.into());
+        assert!(matches!(replay(edited.as_bytes(), None), Err(ReplayError::Invalid(_))));
+        let edited = edit_start(&log, |start| start["state"]["players"][0]["researched"] = serde_json::json!(["Alchemy"]));
+        assert!(matches!(replay(edited.as_bytes(), None), Err(ReplayError::Invalid(_))));
+        let edited = edit_start(&log, |start| start["ruleset"]["mining"]["min_yield"] = 1_000_000.into());
+        assert!(matches!(replay(edited.as_bytes(), None), Err(ReplayError::Invalid(_))));
+        // the given ruleset replaces the invalid one
+        assert!(replay(edited.as_bytes(), Some(&ruleset())).is_ok());
+    }
+
+    #[test]
+    fn log_must_start_with_game() {
+        let (_, log) = recorded_game(3);
+        let log = String::from_utf8(log).unwrap();
+        let steps: Vec<&str> = log.lines().skip(1).collect();
+        assert!(matches!(replay(steps.join("\n").as_bytes(), None), Err(ReplayError::Invalid(_))));
+        assert!(matches!(replay("".as_bytes(), None), Err(ReplayError::Invalid(_))));
+        assert!(matches!(replay("{".as_bytes(), None), Err(ReplayError::Parse(1, _))));
+    }
+}
@@ -0,0 +1,70 @@
+use serde::{Deserialize, Serialize};
+
+/// Amounts of gold, wood and food. Used both for the stock of a player
+/// and for costs, upkeep and production of buildings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Resources {
+    pub gold: u32,
+    pub wood: u32,
+    pub food: u32,
+}
+
+impl Resources {
+    pub fn can_afford(&self, price: &Resources) -> bool {
+        self.gold >= price.gold && self.wood >= price.wood && self.food >= price.food
+    }
+
+    /// Pays the price, or returns `false` and leaves resources untouched if they are not enough.
+    pub fn pay(&mut self, price: &Resources) -> bool {
+        if !self.can_afford(price) {
+            return false;
+        }
+        self.gold -= price.gold;
+        self.wood -= price.wood;
+        self.food -= price.food;
+        true
+    }
+
+    pub fn add(&mut self, other: &Resources) {
+        self.gold = self.gold.saturating_add(other.gold);
+        self.wood = self.wood.saturating_add(other.wood);
+        self.food = self.food.saturating_add(other.food);
+    }
+
+    /// Removes the given percent of every resource, percents above 100 take everything.
+    pub fn lose_percent(&mut self, percent: &Resources) {
+        let lose = |amount: &mut u32, percent: u32| {
+            *amount -= (u64::from(*amount) * u64::from(percent.min(100)) / 100) as u32;
+        };
+        lose(&mut self.gold, percent.gold);
+        lose(&mut self.wood, percent.wood);
+        lose(&mut self.food, percent.food);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pay_subtracts_price() {
+        let mut resources = Resources { gold: 1000, wood: 600, food: 0 };
+        assert!(resources.pay(&Resources { gold: 1000, wood: 500, food: 0 }));
+        assert_eq!(resources, Resources { gold: 0, wood: 100, food: 0 });
+    }
+
+    #[test]
+    fn pay_without_enough_resources_changes_nothing() {
+        let mut resources = Resources { gold: 1000, wood: 400, food: 0 };
+        assert!(!resources.pay(&Resources { gold: 1000, wood: 500, food: 0 }));
+        assert_eq!(resources, Resources { gold: 1000, wood: 400, food: 0 });
+    }
+
+    #[test]
+    fn lose_percent_rounds_loss_down() {
+        let mut resources = Resources { gold: 1000, wood: 15, food: 50 };
+        resources.lose_percent(&Resources { gold: 25, wood: 50, food: 200 });
+        assert_eq!(resources, Resources { gold: 750, wood: 8, food: 0 });
+    }
+}
@@ -0,0 +1,692 @@
+use std::collections::{BTreeSet, HashSet};
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Read};
+
+use serde::{Deserialize, Serialize};
+
+use crate::map::Terrain;
+use crate::resources::Resources;
+
+/// # BuildingType
+/// Kind of building defined by the ruleset.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BuildingType {
+    pub name: String,
+    /// Paid once when the building is built.
+    #[serde(default)]
+    pub cost: Resources,
+    /// Paid at the start of every turn, the building produces nothing when it cannot be paid.
+    #[serde(default)]
+    pub upkeep: Resources,
+    /// Gained at the start of every turn. Food is left standing in the fields until it is harvested.
+    #[serde(default)]
+    pub production: Resources,
+    /// Extra action points in every turn.
+    #[serde(default)]
+    pub action_points: u32,
+}
+
+/// # PopulationRules
+/// How the people of the player eat, grow and work.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PopulationRules {
+    pub initial: u32,
+    /// Food eaten by every person at the start of every turn.
+    pub food_per_person: u32,
+    /// People born in a turn in which everyone has eaten and there is food for another meal.
+    pub growth: u32,
+    /// Crops one person can bring in with one harvest.
+    pub harvest_per_person: u32,
+}
+
+impl Default for PopulationRules {
+    fn default() -> Self {
+        PopulationRules {
+            initial: 5,
+            food_per_person: 10,
+            growth: 1,
+            harvest_per_person: 50,
+        }
+    }
+}
+
+/// # ActionRules
+/// How many actions a player can do in a turn.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ActionRules {
+    /// Action points at the start of every turn, before the points of the buildings.
+    pub points_per_turn: u32,
+    pub costs: ActionCosts,
+}
+
+impl Default for ActionRules {
+    fn default() -> Self {
+        ActionRules {
+            points_per_turn: 1,
+            costs: ActionCosts::default(),
+        }
+    }
+}
+
+/// Action points paid for every kind of action.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ActionCosts {
+    pub build: u32,
+    pub harvest: u32,
+    pub mine: u32,
+    pub explore: u32,
+    pub research: u32,
+    pub trade: u32,
+}
+
+impl ActionCosts {
+    /// Cost of the cheapest action, the turn ends when the player cannot pay it.
+    pub fn cheapest(&self) -> u32 {
+        *[self.build, self.harvest, self.mine, self.explore, self.research, self.trade]
+            .iter()
+            .min()
+            .expect("there are actions")
+    }
+}
+
+impl Default for ActionCosts {
+    fn default() -> Self {
+        ActionCosts {
+            build: 1,
+            harvest: 1,
+            mine: 1,
+            explore: 1,
+            research: 1,
+            trade: 1,
+        }
+    }
+}
+
+/// # MiningRules
+/// How much gold and wood the mines of every player give.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MiningRules {
+    /// Smallest amount mined by one action.
+    pub min_yield: u32,
+    /// Largest amount mined by one action.
+    pub max_yield: u32,
+    /// Gold and wood which can be mined in total, the mines give nothing once they are depleted.
+    pub deposits: Resources,
+}
+
+impl Default for MiningRules {
+    fn default() -> Self {
+        MiningRules {
+            min_yield: 500,
+            max_yield: 500,
+            deposits: Resources {
+                gold: u32::MAX,
+                wood: u32::MAX,
+                food: 0,
+            },
+        }
+    }
+}
+
+/// # MapRules
+/// Size of the map and how its terrain is generated.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MapRules {
+    pub width: usize,
+    pub height: usize,
+    /// Distance from the home which every player has explored from the start.
+    pub sight: usize,
+    /// Tiles revealed by one exploration.
+    pub explore_tiles: usize,
+    pub terrain: Vec<TerrainRules>,
+}
+
+impl Default for MapRules {
+    fn default() -> Self {
+        MapRules {
+            width: 10,
+            height: 10,
+            sight: 1,
+            explore_tiles: 3,
+            terrain: vec![TerrainRules {
+                terrain: Terrain::Plains,
+                weight: 1,
+                mining_bonus: Resources::default(),
+            }],
+        }
+    }
+}
+
+/// # MarketRules
+/// How the prices of the [market](../market/struct.Market.html) move.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MarketRules {
+    /// Value of 100 units of every commodity when nobody traded for a while.
+    pub base_prices: Resources,
+    /// Percent by which the price of a commodity changes for every 100 units sold or bought.
+    pub impact_percent: u32,
+    /// Percent of the way back to the base price which the prices move every turn.
+    pub recovery_percent: u32,
+}
+
+impl Default for MarketRules {
+    fn default() -> Self {
+        MarketRules {
+            base_prices: Resources { gold: 100, wood: 100, food: 100 },
+            impact_percent: 5,
+            recovery_percent: 25,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TerrainRules {
+    pub terrain: Terrain,
+    /// How often the terrain is generated compared to the others.
+    pub weight: u32,
+    /// Percent added to the mining yield for every explored tile of this terrain
+    /// next to the territory of the player.
+    #[serde(default)]
+    pub mining_bonus: Resources,
+}
+
+/// # RandomEvent
+/// Something which can happen to each player at the start of a turn, after the buildings produced.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RandomEvent {
+    pub name: String,
+    /// Shown to the player when the event happens.
+    pub description: String,
+    /// Chance to happen in a turn, from 0 to 1.
+    pub chance: f64,
+    /// Percent by which the crops in the fields grow, negative when they are destroyed.
+    #[serde(default)]
+    pub crops_percent: i32,
+    /// Percent of every stored resource which is lost.
+    #[serde(default)]
+    pub lost_percent: Resources,
+    #[serde(default)]
+    pub gained: Resources,
+}
+
+/// # Technology
+/// Research which unlocks new buildings or upgrades existing ones once it is finished.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Technology {
+    pub name: String,
+    /// Paid at the start of every turn of the research, the research does not advance
+    /// in a turn in which it cannot be paid.
+    #[serde(default)]
+    pub cost_per_turn: Resources,
+    /// Turns of research needed.
+    pub turns: u32,
+    /// Technologies which have to be researched before this one.
+    #[serde(default)]
+    pub requires: Vec<String>,
+    /// Buildings which cannot be built until this technology or another one unlocking them is researched.
+    #[serde(default)]
+    pub unlocks: Vec<String>,
+    #[serde(default)]
+    pub upgrades: Vec<Upgrade>,
+}
+
+/// Improvement of all buildings of a type, existing and future ones.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Upgrade {
+    pub building: String,
+    /// Added to the production of the building.
+    #[serde(default)]
+    pub production: Resources,
+}
+
+/// Goal which wins the game as soon as it is reached.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum VictoryCondition {
+    /// Have at least this much of every resource.
+    Resources { target: Resources },
+    Buildings { building: String, count: u32 },
+    Population { target: u32 },
+    /// Reach the last turn with anyone alive.
+    Survive,
+}
+
+impl fmt::Display for VictoryCondition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            VictoryCondition::Resources { target } => write!(
+                f,
+                "gather {} gold, {} wood and {} food",
+                target.gold, target.wood, target.food
+            ),
+            VictoryCondition::Buildings { building, count } => write!(f, "build {} x {}", count, building),
+            VictoryCondition::Population { target } => write!(f, "reach population of {}", target),
+            VictoryCondition::Survive => write!(f, "survive until the end"),
+        }
+    }
+}
+
+/// # ScoringRules
+/// Points for what the player has at the end of the game.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScoringRules {
+    /// Points for every unit of each resource.
+    pub resource_weights: Resources,
+    pub per_building: u32,
+    pub per_person: u32,
+    pub victory_bonus: u32,
+    /// Points for every turn left when the game was won early.
+    pub per_turn_left: u32,
+}
+
+impl Default for ScoringRules {
+    fn default() -> Self {
+        ScoringRules {
+            resource_weights: Resources { gold: 1, wood: 1, food: 1 },
+            per_building: 100,
+            per_person: 10,
+            victory_bonus: 1000,
+            per_turn_left: 100,
+        }
+    }
+}
+
+fn default_victory() -> Vec<VictoryCondition> {
+    vec![VictoryCondition::Survive]
+}
+
+/// # Ruleset
+/// Rules of the game loaded from a JSON file, so that they can be balanced without recompiling.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Ruleset {
+    pub buildings: Vec<BuildingType>,
+    #[serde(default)]
+    pub starting_resources: Resources,
+    #[serde(default)]
+    pub population: PopulationRules,
+    #[serde(default)]
+    pub actions: ActionRules,
+    #[serde(default)]
+    pub mining: MiningRules,
+    #[serde(default)]
+    pub map: MapRules,
+    #[serde(default)]
+    pub market: MarketRules,
+    #[serde(default)]
+    pub events: Vec<RandomEvent>,
+    #[serde(default)]
+    pub technologies: Vec<Technology>,
+    /// Reaching any of them wins the game, the game is lost when none is reached by the last turn.
+    #[serde(default = "default_victory")]
+    pub victory: Vec<VictoryCondition>,
+    #[serde(default)]
+    pub scoring: ScoringRules,
+}
+
+#[derive(Debug)]
+pub enum RulesetError {
+    Io(io::Error),
+    Parse(serde_json::Error),
+    /// Ruleset is well-formed but does not make sense.
+    Invalid(String),
+}
+
+impl fmt::Display for RulesetError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RulesetError::Io(error) => write!(f, "Cannot read ruleset: {}", error),
+            RulesetError::Parse(error) => write!(f, "Cannot parse ruleset: {}", error),
+            RulesetError::Invalid(message) => write!(f, "Invalid ruleset: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for RulesetError {}
+
+impl From<io::Error> for RulesetError {
+    fn from(error: io::Error) -> Self {
+        RulesetError::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for RulesetError {
+    fn from(error: serde_json::Error) -> Self {
+        RulesetError::Parse(error)
+    }
+}
+
+impl Ruleset {
+    pub fn load(rules_file: &str) -> Result<Ruleset, RulesetError> {
+        let mut input_file = File::open(rules_file)?;
+        let mut data = String::new();
+        input_file.read_to_string(&mut data)?;
+        Ruleset::from_json(&data)
+    }
+
+    pub fn from_json(data: &str) -> Result<Ruleset, RulesetError> {
+        let ruleset: Ruleset = serde_json::from_str(data)?;
+        ruleset.validate()?;
+        Ok(ruleset)
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), RulesetError> {
+        if self.buildings.is_empty() {
+            return Err(RulesetError::Invalid("There are no buildings".to_string()));
+        }
+        let mut names = HashSet::new();
+        for building in &self.buildings {
+            if building.name.trim().is_empty() {
+                return Err(RulesetError::Invalid("Building has no name".to_string()));
+            }
+            if !names.insert(building.name.as_str()) {
+                return Err(RulesetError::Invalid(format!("Building '{}' is defined twice", building.name)));
+            }
+        }
+        if self.actions.points_per_turn == 0 {
+            return Err(RulesetError::Invalid("Players need at least one action point per turn".to_string()));
+        }
+        if self.actions.costs.cheapest() == 0 {
+            return Err(RulesetError::Invalid("Every action must cost at least one action point".to_string()));
+        }
+        if self.mining.min_yield > self.mining.max_yield {
+            return Err(RulesetError::Invalid("Minimal mining yield is above the maximal one".to_string()));
+        }
+        // with fewer rows the homes in the top and the bottom row would be in the same row
+        if self.map.width == 0 || self.map.height < 4 {
+            return Err(RulesetError::Invalid("Map must be at least four tiles high".to_string()));
+        }
+        // the map sums the weights, so they have to fit together
+        match self.map.terrain.iter().try_fold(0u32, |total, terrain| total.checked_add(terrain.weight)) {
+            Some(0) => return Err(RulesetError::Invalid("Map has no terrain to generate".to_string())),
+            None => return Err(RulesetError::Invalid("Terrain weights are too large".to_string())),
+            Some(_) => {}
+        }
+        let prices = &self.market.base_prices;
+        if prices.gold == 0 || prices.wood == 0 || prices.food == 0 {
+            return Err(RulesetError::Invalid("Market prices must not be zero".to_string()));
+        }
+        if self.market.recovery_percent > 100 {
+            return Err(RulesetError::Invalid("Market prices cannot recover more than 100 %".to_string()));
+        }
+        for event in &self.events {
+            if !(0.0..=1.0).contains(&event.chance) {
+                return Err(RulesetError::Invalid(format!("Chance of event '{}' is not between 0 and 1", event.name)));
+            }
+            if event.crops_percent < -100 {
+                return Err(RulesetError::Invalid(format!("Event '{}' destroys more than all crops", event.name)));
+            }
+        }
+        self.validate_technologies()?;
+        for condition in &self.victory {
+            if let VictoryCondition::Buildings { building, .. } = condition {
+                if self.building(building).is_none() {
+                    let message = format!("Victory condition needs unknown building '{}'", building);
+                    return Err(RulesetError::Invalid(message));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_technologies(&self) -> Result<(), RulesetError> {
+        let mut names = HashSet::new();
+        for technology in &self.technologies {
+            if !names.insert(technology.name.as_str()) {
+                return Err(RulesetError::Invalid(format!("Technology '{}' is defined twice", technology.name)));
+            }
+            if technology.turns == 0 {
+                return Err(RulesetError::Invalid(format!("Technology '{}' takes no time", technology.name)));
+            }
+            let buildings = technology.unlocks.iter().chain(technology.upgrades.iter().map(|upgrade| &upgrade.building));
+            if let Some(building) = buildings.into_iter().find(|name| self.building(name).is_none()) {
+                let message = format!("Technology '{}' needs unknown building '{}'", technology.name, building);
+                return Err(RulesetError::Invalid(message));
+            }
+        }
+        for technology in &self.technologies {
+            if let Some(required) = technology.requires.iter().find(|name| !names.contains(name.as_str())) {
+                let message = format!("Technology '{}' requires unknown technology '{}'", technology.name, required);
+                return Err(RulesetError::Invalid(message));
+            }
+        }
+
+        // technologies are researchable when all their prerequisites are,
+        // whatever is left over requires itself through a cycle of prerequisites
+        let mut researchable = HashSet::new();
+        loop {
+            let next: Vec<&str> = self
+                .technologies
+                .iter()
+                .filter(|technology| !researchable.contains(technology.name.as_str()))
+                .filter(|technology| technology.requires.iter().all(|name| researchable.contains(name.as_str())))
+                .map(|technology| technology.name.as_str())
+                .collect();
+            if next.is_empty() {
+                break;
+            }
+            researchable.extend(next);
+        }
+        match self.technologies.iter().find(|technology| !researchable.contains(technology.name.as_str())) {
+            Some(technology) => {
+                let message = format!("Prerequisites of technology '{}' form a cycle", technology.name);
+                Err(RulesetError::Invalid(message))
+            }
+            None => Ok(()),
+        }
+    }
+
+    pub fn building(&self, name: &str) -> Option<&BuildingType> {
+        self.buildings.iter().find(|building| building.name == name)
+    }
+
+    pub fn technology(&self, name: &str) -> Option<&Technology> {
+        self.technologies.iter().find(|technology| technology.name == name)
+    }
+
+    /// Technologies which unlock the building, it can be built from the start when there are none.
+    pub fn unlocked_by<'a>(&'a self, building: &'a str) -> impl Iterator<Item = &'a Technology> + 'a {
+        self.technologies
+            .iter()
+            .filter(move |technology| technology.unlocks.iter().any(|name| name == building))
+    }
+
+    /// Largest number of players for whom the map has homes, one per column.
+    pub fn max_players(&self) -> usize {
+        self.map.width
+    }
+
+    /// Action points in every turn of a player with the given buildings.
+    pub fn action_points<'a>(&self, buildings: impl Iterator<Item = &'a str>) -> u32 {
+        buildings
+            .filter_map(|name| self.building(name))
+            .fold(self.actions.points_per_turn, |points, building| {
+                points.saturating_add(building.action_points)
+            })
+    }
+
+    /// Production of the building with the upgrades of the researched technologies.
+    pub fn production(&self, building: &BuildingType, researched: &BTreeSet<String>) -> Resources {
+        let mut production = building.production;
+        for technology in self.technologies.iter().filter(|technology| researched.contains(&technology.name)) {
+            for upgrade in technology.upgrades.iter().filter(|upgrade| upgrade.building == building.name) {
+                production.add(&upgrade.production);
+            }
+        }
+        production
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_rules_are_valid() {
+        let ruleset = Ruleset::from_json(include_str!("../rules.json")).unwrap();
+        let farm = ruleset.building("Farm").unwrap();
+        assert_eq!(farm.cost, Resources { gold: 1000, wood: 500, food: 0 });
+        assert_eq!(farm.production.food, 100);
+        assert_eq!(farm.upkeep, Resources::default());
+        assert_eq!(ruleset.starting_resources.food, 200);
+    }
+
+    #[test]
+    fn population_rules_have_defaults() {
+        let ruleset = Ruleset::from_json(r#"{"buildings": [{"name": "Hut"}], "population": {"initial": 3}}"#).unwrap();
+        assert_eq!(ruleset.population.initial, 3);
+        assert_eq!(ruleset.population.food_per_person, PopulationRules::default().food_per_person);
+        assert_eq!(ruleset.starting_resources, Resources::default());
+    }
+
+    #[test]
+    fn victory_conditions_are_parsed() {
+        let ruleset = Ruleset::from_json(
+            r#"{
+                "buildings": [{"name": "Hut"}],
+                "victory": [
+                    {"type": "resources", "target": {"gold": 5000}},
+                    {"type": "buildings", "building": "Hut", "count": 3},
+                    {"type": "survive"}
+                ]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            ruleset.victory[1],
+            VictoryCondition::Buildings {
+                building: "Hut".to_string(),
+                count: 3
+            }
+        );
+        assert_eq!(ruleset.victory[0].to_string(), "gather 5000 gold, 0 wood and 0 food");
+    }
+
+    #[test]
+    fn survival_is_default_victory() {
+        let ruleset = Ruleset::from_json(r#"{"buildings": [{"name": "Hut"}]}"#).unwrap();
+        assert_eq!(ruleset.victory, vec![VictoryCondition::Survive]);
+    }
+
+    #[test]
+    fn victory_with_unknown_building_is_rejected() {
+        let json = r#"{"buildings": [{"name": "Hut"}], "victory": [{"type": "buildings", "building": "Castle", "count": 1}]}"#;
+        assert!(matches!(Ruleset::from_json(json), Err(RulesetError::Invalid(_))));
+    }
+
+    #[test]
+    fn random_events_are_parsed() {
+        let ruleset = Ruleset::from_json(include_str!("../rules.json")).unwrap();
+        let drought = ruleset.events.iter().find(|event| event.name == "Drought").unwrap();
+        assert_eq!(drought.crops_percent, -50);
+        assert!(ruleset.mining.min_yield < ruleset.mining.max_yield);
+    }
+
+    #[test]
+    fn map_without_terrain_is_rejected() {
+        let json = r#"{"buildings": [{"name": "Hut"}], "map": {"terrain": []}}"#;
+        assert!(matches!(Ruleset::from_json(json), Err(RulesetError::Invalid(_))));
+        let json = r#"{"buildings": [{"name": "Hut"}], "map": {"terrain": [
+            {"terrain": "plains", "weight": 4294967295},
+            {"terrain": "forest", "weight": 1}
+        ]}}"#;
+        assert!(matches!(Ruleset::from_json(json), Err(RulesetError::Invalid(_))));
+        let json = r#"{"buildings": [{"name": "Hut"}], "map": {"height": 3}}"#;
+        assert!(matches!(Ruleset::from_json(json), Err(RulesetError::Invalid(_))));
+    }
+
+    #[test]
+    fn free_commodity_is_rejected() {
+        let json = r#"{"buildings": [{"name": "Hut"}], "market": {"base_prices": {"gold": 100, "wood": 100}}}"#;
+        assert!(matches!(Ruleset::from_json(json), Err(RulesetError::Invalid(_))));
+        let json = r#"{"buildings": [{"name": "Hut"}], "market": {"impact_percent": 0}}"#;
+        assert_eq!(Ruleset::from_json(json).unwrap().market.base_prices, MarketRules::default().base_prices);
+    }
+
+    #[test]
+    fn action_rules_have_defaults() {
+        let json = r#"{"buildings": [{"name": "Hut"}], "actions": {"points_per_turn": 3, "costs": {"build": 2}}}"#;
+        let actions = Ruleset::from_json(json).unwrap().actions;
+        assert_eq!((actions.points_per_turn, actions.costs.build, actions.costs.mine), (3, 2, 1));
+        assert_eq!(actions.costs.cheapest(), 1);
+        let json = r#"{"buildings": [{"name": "Hut"}], "actions": {"points_per_turn": 0}}"#;
+        assert!(matches!(Ruleset::from_json(json), Err(RulesetError::Invalid(_))));
+    }
+
+    #[test]
+    fn free_action_is_rejected() {
+        let json = r#"{"buildings": [{"name": "Hut"}], "actions": {"costs": {"explore": 0}}}"#;
+        assert!(matches!(Ruleset::from_json(json), Err(RulesetError::Invalid(_))));
+    }
+
+    #[test]
+    fn invalid_chance_is_rejected() {
+        let json = r#"{"buildings": [{"name": "Hut"}], "events": [{"name": "Flood", "description": "", "chance": 1.5}]}"#;
+        assert!(matches!(Ruleset::from_json(json), Err(RulesetError::Invalid(_))));
+    }
+
+    #[test]
+    fn missing_amounts_are_zero() {
+        let ruleset = Ruleset::from_json(r#"{"buildings": [{"name": "Hut", "cost": {"wood": 10}}]}"#).unwrap();
+        let hut = ruleset.building("Hut").unwrap();
+        assert_eq!(hut.cost, Resources { gold: 0, wood: 10, food: 0 });
+        assert_eq!(hut.production, Resources::default());
+    }
+
+    #[test]
+    fn duplicate_buildings_are_rejected() {
+        let result = Ruleset::from_json(r#"{"buildings": [{"name": "Hut"}, {"name": "Hut"}]}"#);
+        assert!(matches!(result, Err(RulesetError::Invalid(_))));
+    }
+
+    #[test]
+    fn upgrades_add_to_production() {
+        let ruleset = Ruleset::from_json(include_str!("../rules.json")).unwrap();
+        let farm = ruleset.building("Farm").unwrap();
+        let mut researched = BTreeSet::new();
+        assert_eq!(ruleset.production(farm, &researched), farm.production);
+        researched.insert("Irrigation".to_string());
+        assert_eq!(ruleset.production(farm, &researched).food, farm.production.food + 50);
+        let names: Vec<_> = ruleset.unlocked_by("Workshop").map(|technology| technology.name.as_str()).collect();
+        assert_eq!(names, vec!["Carpentry"]);
+    }
+
+    #[test]
+    fn prerequisite_cycles_are_rejected() {
+        let json = r#"{
+            "buildings": [{"name": "Hut"}],
+            "technologies": [
+                {"name": "Fire", "turns": 1},
+                {"name": "Wheel", "turns": 1, "requires": ["Fire", "Cart"]},
+                {"name": "Cart", "turns": 1, "requires": ["Wheel"]}
+            ]
+        }"#;
+        let error = Ruleset::from_json(json).unwrap_err();
+        assert_eq!(error.to_string(), "Invalid ruleset: Prerequisites of technology 'Wheel' form a cycle");
+        let json = r#"{"buildings": [{"name": "Hut"}], "technologies": [{"name": "Fire", "turns": 1, "requires": ["Fire"]}]}"#;
+        assert!(matches!(Ruleset::from_json(json), Err(RulesetError::Invalid(_))));
+    }
+
+    #[test]
+    fn technologies_must_refer_to_known_names() {
+        let json = r#"{"buildings": [{"name": "Hut"}], "technologies": [{"name": "Fire", "turns": 1, "requires": ["Flint"]}]}"#;
+        assert!(matches!(Ruleset::from_json(json), Err(RulesetError::Invalid(_))));
+        let json = r#"{"buildings": [{"name": "Hut"}], "technologies": [{"name": "Fire", "turns": 1, "unlocks": ["Forge"]}]}"#;
+        assert!(matches!(Ruleset::from_json(json), Err(RulesetError::Invalid(_))));
+        let json = r#"{"buildings": [{"name": "Hut"}], "technologies": [{"name": "Fire", "turns": 0}]}"#;
+        assert!(matches!(Ruleset::from_json(json), Err(RulesetError::Invalid(_))));
+    }
+
+    #[test]
+    fn ruleset_without_buildings_is_rejected() {
+        assert!(Ruleset::from_json(r#"{"buildings": []}"#).is_err());
+        assert!(Ruleset::from_json(r#"{"buildings": [{"cost": {}}]}"#).is_err());
+    }
+}
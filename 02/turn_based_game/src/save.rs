@@ -0,0 +1,434 @@
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Read, Write};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::map::{Building, Map, Position};
+use crate::market::Market;
+use crate::ruleset::Ruleset;
+use crate::GameState;
+
+/// Version of the save format written by this build.
+/// Bump it whenever `GameState` changes and add a step to [`migrate`](fn.migrate.html).
+pub const SAVE_VERSION: u32 = 8;
+
+#[derive(Serialize, Deserialize)]
+struct SaveFile<T> {
+    version: u32,
+    game: T,
+}
+
+#[derive(Debug)]
+pub enum SaveError {
+    Io(io::Error),
+    Parse(serde_json::Error),
+    /// Save was written by a newer version of the game.
+    UnsupportedVersion(u32),
+    /// Save contains a building which is not in the current ruleset.
+    UnknownBuilding(String),
+    /// Save is well-formed but does not make sense.
+    Invalid(String),
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SaveError::Io(error) => write!(f, "Cannot access save: {}", error),
+            SaveError::Parse(error) => write!(f, "Save is corrupted: {}", error),
+            SaveError::UnsupportedVersion(version) => {
+                write!(f, "Save version {} is not supported, the newest one is {}", version, SAVE_VERSION)
+            }
+            SaveError::UnknownBuilding(name) => write!(f, "Save contains unknown building '{}'", name),
+            SaveError::Invalid(message) => write!(f, "Save is invalid: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for SaveError {}
+
+impl From<io::Error> for SaveError {
+    fn from(error: io::Error) -> Self {
+        SaveError::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for SaveError {
+    fn from(error: serde_json::Error) -> Self {
+        SaveError::Parse(error)
+    }
+}
+
+pub fn save(state: &GameState, save_file: &str) -> Result<(), SaveError> {
+    let mut output_file = File::create(save_file)?;
+    output_file.write_all(to_json(state)?.as_bytes())?;
+    Ok(())
+}
+
+pub fn load(save_file: &str, ruleset: &Ruleset) -> Result<GameState, SaveError> {
+    let mut input_file = File::open(save_file)?;
+    let mut data = String::new();
+    input_file.read_to_string(&mut data)?;
+    from_json(&data, ruleset)
+}
+
+fn to_json(state: &GameState) -> Result<String, SaveError> {
+    let save_file = SaveFile {
+        version: SAVE_VERSION,
+        game: state,
+    };
+    Ok(serde_json::to_string_pretty(&save_file)?)
+}
+
+fn from_json(data: &str, ruleset: &Ruleset) -> Result<GameState, SaveError> {
+    let save_file: SaveFile<Value> = serde_json::from_str(data)?;
+    upgrade(save_file.version, save_file.game, ruleset)
+}
+
+/// Migrates the game saved in the given version and checks that it can be played with the ruleset.
+pub(crate) fn upgrade(version: u32, game: Value, ruleset: &Ruleset) -> Result<GameState, SaveError> {
+    let state: GameState = serde_json::from_value(migrate(version, game, ruleset)?)?;
+    if state.current_player >= state.players.len() {
+        return Err(SaveError::Invalid("Player on turn does not exist".to_string()));
+    }
+    if state.map.tiles.len() != state.map.width * state.map.height {
+        return Err(SaveError::Invalid("Map does not match its size".to_string()));
+    }
+    for player in &state.players {
+        if player.territory().any(|position| state.map.terrain(position).is_none()) {
+            return Err(SaveError::Invalid(format!("Territory of {} is outside the map", player.name)));
+        }
+        for building in &player.buildings {
+            if ruleset.building(&building.name).is_none() {
+                return Err(SaveError::UnknownBuilding(building.name.clone()));
+            }
+        }
+        let research = player.research.iter().map(|research| &research.technology);
+        if let Some(name) = player.researched.iter().chain(research).find(|name| ruleset.technology(name).is_none()) {
+            return Err(SaveError::Invalid(format!("Save contains unknown technology '{}'", name)));
+        }
+    }
+    Ok(state)
+}
+
+/// Upgrades the saved game from the given version to [`SAVE_VERSION`](constant.SAVE_VERSION.html),
+/// one version at a time.
+fn migrate(version: u32, mut game: Value, ruleset: &Ruleset) -> Result<Value, SaveError> {
+    match version {
+        // version 1 had no population, farms filled the stored food directly
+        1 => {
+            if let Some(fields) = game.as_object_mut() {
+                fields.insert("population".to_string(), ruleset.population.initial.into());
+                fields.insert("crops".to_string(), 0.into());
+            }
+            migrate(2, game, ruleset)
+        }
+        // version 2 had a single player
+        2 => {
+            if let Some(fields) = game.as_object_mut() {
+                let mut player = json!({"name": "Player"});
+                for field in &["resources", "buildings", "population", "crops"] {
+                    if let Some(value) = fields.remove(*field) {
+                        player[*field] = value;
+                    }
+                }
+                fields.insert("current_player".to_string(), 0.into());
+                fields.insert("players".to_string(), json!([player]));
+            }
+            migrate(3, game, ruleset)
+        }
+        // version 3 had no randomness, mines never ran out
+        3 => {
+            if let Some(fields) = game.as_object_mut() {
+                fields.insert("seed".to_string(), 0.into());
+                if let Some(Value::Array(players)) = fields.get_mut("players") {
+                    for player in players.iter_mut().filter_map(Value::as_object_mut) {
+                        player.insert("deposits".to_string(), serde_json::to_value(ruleset.mining.deposits)?);
+                    }
+                }
+            }
+            migrate(4, game, ruleset)
+        }
+        // version 4 had no map, buildings were only names
+        4 => {
+            if let Some(fields) = game.as_object_mut() {
+                let seed = fields.get("seed").and_then(Value::as_u64).unwrap_or(0);
+                let count = fields.get("players").and_then(Value::as_array).map_or(0, Vec::len);
+                let map = Map::generate(&ruleset.map, seed, count.max(1));
+                if let Some(Value::Array(players)) = fields.get_mut("players") {
+                    place_on_map(players, &map, ruleset)?;
+                }
+                fields.insert("map".to_string(), serde_json::to_value(map)?);
+            }
+            migrate(5, game, ruleset)
+        }
+        // version 5 had no research
+        5 => {
+            if let Some(Value::Array(players)) = game.get_mut("players") {
+                for player in players.iter_mut().filter_map(Value::as_object_mut) {
+                    player.insert("researched".to_string(), json!([]));
+                    player.insert("research".to_string(), Value::Null);
+                }
+            }
+            migrate(6, game, ruleset)
+        }
+        // version 6 had no market
+        6 => {
+            if let Some(fields) = game.as_object_mut() {
+                fields.insert("market".to_string(), serde_json::to_value(Market::new(&ruleset.market))?);
+            }
+            migrate(7, game, ruleset)
+        }
+        // version 7 had one action per turn, every player gets a full turn of action points
+        7 => {
+            if let Some(Value::Array(players)) = game.get_mut("players") {
+                for player in players.iter_mut().filter(|player| player.is_object()) {
+                    let buildings: Vec<Building> = serde_json::from_value(player["buildings"].clone())?;
+                    let points = ruleset.action_points(buildings.iter().map(|building| building.name.as_str()));
+                    player["action_points"] = points.into();
+                }
+            }
+            migrate(8, game, ruleset)
+        }
+        SAVE_VERSION => Ok(game),
+        _ => Err(SaveError::UnsupportedVersion(version)),
+    }
+}
+
+/// Gives the players of an old save their homes and puts their buildings
+/// on the free tiles nearest to the home.
+fn place_on_map(players: &mut [Value], map: &Map, ruleset: &Ruleset) -> Result<(), SaveError> {
+    let count = players.len();
+    let mut occupied: Vec<Position> = (0..count).map(|index| map.home(index, count)).collect();
+    for (index, player) in players.iter_mut().enumerate().filter(|(_, player)| player.is_object()) {
+        let home = map.home(index, count);
+        let mut tiles: Vec<Position> = (0..map.height).flat_map(|y| (0..map.width).map(move |x| (x, y))).collect();
+        tiles.sort_by_key(|&(x, y)| (x.max(home.0) - x.min(home.0)).max(y.max(home.1) - y.min(home.1)));
+
+        let names: Vec<String> = serde_json::from_value(player["buildings"].take())?;
+        let mut explored: Vec<Position> = map.around(home, ruleset.map.sight);
+        let mut buildings = Vec::new();
+        for name in names {
+            let position = *tiles
+                .iter()
+                .find(|tile| !occupied.contains(tile))
+                .ok_or_else(|| SaveError::Invalid("Buildings do not fit on the map".to_string()))?;
+            occupied.push(position);
+            explored.push(position);
+            buildings.push(Building { name, position });
+        }
+        explored.sort_unstable();
+        explored.dedup();
+        player["buildings"] = serde_json::to_value(buildings)?;
+        player["home"] = serde_json::to_value(home)?;
+        player["explored"] = serde_json::to_value(explored)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::Resources;
+    use crate::{Game, PlayerState, Research};
+
+    fn ruleset() -> Ruleset {
+        Ruleset::from_json(include_str!("../rules.json")).unwrap()
+    }
+
+    fn state() -> GameState {
+        let mut computer = PlayerState::new("Computer", &ruleset());
+        computer.strategy = Some("greedy".to_string());
+        let players = vec![PlayerState::new("Human", &ruleset()), computer];
+        let game = Game::with_players(ruleset(), players, 10).with_seed(42);
+        let sites = game.building_sites(1);
+
+        let mut state = game.state().clone();
+        state.current_turn = 3;
+        state.current_player = 1;
+        let computer = &mut state.players[1];
+        computer.resources = Resources { gold: 100, wood: 200, food: 300 };
+        computer.buildings = vec![
+            Building {
+                name: "Farm".to_string(),
+                position: sites[0],
+            },
+            Building {
+                name: "Sawmill".to_string(),
+                position: sites[1],
+            },
+        ];
+        state.market.prices.gold = 70;
+        let computer = &mut state.players[1];
+        computer.population = 7;
+        computer.crops = 50;
+        computer.deposits = Resources { gold: 1000, wood: 0, food: 0 };
+        computer.researched.insert("Carpentry".to_string());
+        computer.research = Some(Research {
+            technology: "Deep Mining".to_string(),
+            turns_done: 1,
+        });
+        state
+    }
+
+    #[test]
+    fn saved_game_loads_unchanged() {
+        let json = to_json(&state()).unwrap();
+        assert_eq!(from_json(&json, &ruleset()).unwrap(), state());
+    }
+
+    #[test]
+    fn save_contains_version() {
+        let json: Value = serde_json::from_str(&to_json(&state()).unwrap()).unwrap();
+        assert_eq!(json["version"], SAVE_VERSION);
+    }
+
+    #[test]
+    fn version_1_is_migrated() {
+        let json = r#"{
+            "version": 1,
+            "game": {
+                "current_turn": 3,
+                "end_turn": 10,
+                "resources": {"gold": 100, "wood": 200, "food": 300},
+                "buildings": ["Farm"]
+            }
+        }"#;
+        let state = from_json(json, &ruleset()).unwrap();
+        let player = &state.players[0];
+        assert_eq!(player.resources.food, 300);
+        assert_eq!(player.population, ruleset().population.initial);
+        assert_eq!(player.crops, 0);
+    }
+
+    #[test]
+    fn version_2_is_migrated() {
+        let json = r#"{
+            "version": 2,
+            "game": {
+                "current_turn": 3,
+                "end_turn": 10,
+                "resources": {"gold": 100, "wood": 200, "food": 300},
+                "buildings": ["Farm"],
+                "population": 4,
+                "crops": 20
+            }
+        }"#;
+        let state = from_json(json, &ruleset()).unwrap();
+        assert_eq!(state.current_player, 0);
+        assert_eq!(state.seed, 0);
+        let player = &state.players[0];
+        assert_eq!(player.name, "Player");
+        assert_eq!(player.strategy, None);
+        assert_eq!((player.population, player.crops), (4, 20));
+        assert_eq!(player.deposits, ruleset().mining.deposits);
+        assert_eq!(player.buildings[0].name, "Farm");
+    }
+
+    #[test]
+    fn version_4_buildings_are_placed_next_to_home() {
+        let json = r#"{
+            "version": 4,
+            "game": {
+                "current_turn": 3,
+                "end_turn": 10,
+                "seed": 7,
+                "current_player": 0,
+                "players": [{
+                    "name": "Player",
+                    "resources": {"gold": 100},
+                    "buildings": ["Farm", "Sawmill"],
+                    "population": 4,
+                    "crops": 20,
+                    "deposits": {"gold": 1000, "wood": 1000}
+                }]
+            }
+        }"#;
+        let state = from_json(json, &ruleset()).unwrap();
+        assert_eq!(state.map, Map::generate(&ruleset().map, 7, 1));
+        let player = &state.players[0];
+        assert_eq!(player.home, state.map.home(0, 1));
+        for building in &player.buildings {
+            assert_ne!(building.position, player.home);
+            assert!(state.map.around(player.home, 1).contains(&building.position));
+            assert!(player.explored.contains(&building.position));
+        }
+        assert_ne!(player.buildings[0].position, player.buildings[1].position);
+    }
+
+    #[test]
+    fn version_7_players_get_full_turn_of_action_points() {
+        let mut json: Value = serde_json::from_str(&to_json(&state()).unwrap()).unwrap();
+        json["version"] = 7.into();
+        json["game"]["players"][1]["buildings"][0]["name"] = "Town Hall".into();
+        for player in json["game"]["players"].as_array_mut().unwrap() {
+            player.as_object_mut().unwrap().remove("action_points");
+        }
+        let state = from_json(&json.to_string(), &ruleset()).unwrap();
+        let points = ruleset().actions.points_per_turn;
+        assert_eq!(state.players[0].action_points, points);
+        assert_eq!(state.players[1].action_points, points + 1);
+    }
+
+    #[test]
+    fn version_5_has_nothing_researched() {
+        let mut json: Value = serde_json::from_str(&to_json(&state()).unwrap()).unwrap();
+        json["version"] = 5.into();
+        json["game"].as_object_mut().unwrap().remove("market");
+        for player in json["game"]["players"].as_array_mut().unwrap() {
+            let player = player.as_object_mut().unwrap();
+            player.remove("researched");
+            player.remove("research");
+            player.remove("action_points");
+        }
+        let mut expected = state();
+        expected.players[1].researched.clear();
+        expected.players[1].research = None;
+        expected.market = Market::new(&ruleset().market);
+        assert_eq!(from_json(&json.to_string(), &ruleset()).unwrap(), expected);
+    }
+
+    #[test]
+    fn newer_version_is_rejected() {
+        let json = r#"{"version": 999, "game": {}}"#;
+        assert!(matches!(from_json(json, &ruleset()), Err(SaveError::UnsupportedVersion(999))));
+    }
+
+    #[test]
+    fn unknown_building_is_rejected() {
+        let mut state = state();
+        state.players[1].buildings[0].name = "Castle".to_string();
+        let json = to_json(&state).unwrap();
+        assert!(matches!(from_json(&json, &ruleset()), Err(SaveError::UnknownBuilding(name)) if name == "Castle"));
+    }
+
+    #[test]
+    fn unknown_technology_is_rejected() {
+        let mut state = state();
+        state.players[0].researched.insert("Alchemy".to_string());
+        let json = to_json(&state).unwrap();
+        assert!(matches!(from_json(&json, &ruleset()), Err(SaveError::Invalid(_))));
+    }
+
+    #[test]
+    fn missing_player_on_turn_is_rejected() {
+        let mut state = state();
+        state.players.pop();
+        let json = to_json(&state).unwrap();
+        assert!(matches!(from_json(&json, &ruleset()), Err(SaveError::Invalid(_))));
+    }
+
+    #[test]
+    fn building_outside_map_is_rejected() {
+        let mut state = state();
+        state.players[1].buildings[0].position = (100, 0);
+        let json = to_json(&state).unwrap();
+        assert!(matches!(from_json(&json, &ruleset()), Err(SaveError::Invalid(_))));
+    }
+
+    #[test]
+    fn corrupted_save_is_rejected() {
+        assert!(matches!(from_json("{", &ruleset()), Err(SaveError::Parse(_))));
+    }
+}
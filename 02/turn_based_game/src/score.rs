@@ -0,0 +1,226 @@
+use std::fmt;
+use std::fs::File;
+use std::io::{self, ErrorKind, Read, Write};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Game, Outcome, PlayerState};
+
+/// # Score
+/// Points for a finished game, broken down by where they came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Score {
+    pub resources: u32,
+    pub buildings: u32,
+    pub population: u32,
+    /// Bonus for winning, bigger for winning early.
+    pub victory: u32,
+}
+
+impl Score {
+    /// Score of the player with the given index.
+    pub fn of(game: &Game, player: usize) -> Score {
+        let rules = &game.ruleset().scoring;
+        let state = game.state();
+        let weights = &rules.resource_weights;
+        let PlayerState {
+            resources,
+            buildings,
+            population,
+            ..
+        } = game.player(player);
+        let resources = resources
+            .gold
+            .saturating_mul(weights.gold)
+            .saturating_add(resources.wood.saturating_mul(weights.wood))
+            .saturating_add(resources.food.saturating_mul(weights.food));
+        let victory = match game.outcome(player) {
+            Some(Outcome::Won(_)) => {
+                let turns_left = state.end_turn.saturating_sub(state.current_turn);
+                rules.victory_bonus.saturating_add(turns_left.saturating_mul(rules.per_turn_left))
+            }
+            _ => 0,
+        };
+        Score {
+            resources,
+            buildings: (buildings.len() as u32).saturating_mul(rules.per_building),
+            population: population.saturating_mul(rules.per_person),
+            victory,
+        }
+    }
+
+    pub fn total(&self) -> u32 {
+        self.resources
+            .saturating_add(self.buildings)
+            .saturating_add(self.population)
+            .saturating_add(self.victory)
+    }
+}
+
+impl fmt::Display for Score {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Resources:  {:>8}", self.resources)?;
+        writeln!(f, "Buildings:  {:>8}", self.buildings)?;
+        writeln!(f, "Population: {:>8}", self.population)?;
+        writeln!(f, "Victory:    {:>8}", self.victory)?;
+        write!(f, "Total:      {:>8}", self.total())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HighScore {
+    pub name: String,
+    pub score: u32,
+    pub won: bool,
+    /// Turns played.
+    pub turns: u32,
+}
+
+/// # HighScores
+/// Best scores of all time, kept in a local file between runs.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct HighScores {
+    /// Sorted from the best.
+    pub entries: Vec<HighScore>,
+}
+
+impl HighScores {
+    pub const MAX_ENTRIES: usize = 10;
+
+    /// Loads the table, a missing file is an empty table.
+    pub fn load(high_scores_file: &str) -> Result<HighScores, io::Error> {
+        let mut input_file = match File::open(high_scores_file) {
+            Ok(file) => file,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(HighScores::default()),
+            Err(error) => return Err(error),
+        };
+        let mut data = String::new();
+        input_file.read_to_string(&mut data)?;
+        let high_scores: HighScores = serde_json::from_str(&data)?;
+        Ok(high_scores)
+    }
+
+    pub fn save(&self, high_scores_file: &str) -> Result<(), io::Error> {
+        let mut output_file = File::create(high_scores_file)?;
+        output_file.write_all(serde_json::to_string_pretty(self)?.as_bytes())
+    }
+
+    /// Adds the entry if it is good enough, returns its position counted from 0.
+    pub fn add(&mut self, entry: HighScore) -> Option<usize> {
+        // later entries with the same score go below the earlier ones
+        let position = self
+            .entries
+            .iter()
+            .position(|other| other.score < entry.score)
+            .unwrap_or(self.entries.len());
+        if position >= HighScores::MAX_ENTRIES {
+            return None;
+        }
+        self.entries.insert(position, entry);
+        self.entries.truncate(HighScores::MAX_ENTRIES);
+        Some(position)
+    }
+}
+
+impl fmt::Display for HighScores {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (index, entry) in self.entries.iter().enumerate() {
+            let result = if entry.won { "won" } else { "lost" };
+            writeln!(
+                f,
+                "{:>2}. {:<20} {:>8} ({} in {} turns)",
+                index + 1,
+                entry.name,
+                entry.score,
+                result,
+                entry.turns
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::Resources;
+    use crate::ruleset::Ruleset;
+    use crate::map::Building;
+    use crate::GameAction;
+
+    fn entry(name: &str, score: u32) -> HighScore {
+        HighScore {
+            name: name.to_string(),
+            score,
+            won: true,
+            turns: 10,
+        }
+    }
+
+    #[test]
+    fn score_breakdown() {
+        let ruleset = Ruleset::from_json(include_str!("../rules.json")).unwrap();
+        let mut state = Game::new(ruleset.clone(), 10).state().clone();
+        state.current_turn = 6;
+        let player = &mut state.players[0];
+        player.resources = Resources { gold: 3000, wood: 3000, food: 1000 };
+        player.buildings = vec![Building {
+            name: "Farm".to_string(),
+            position: (0, 0),
+        }];
+        player.population = 4;
+        let game = Game::from_state(ruleset, state);
+        let score = Score::of(&game, 0);
+        assert_eq!(
+            score,
+            Score {
+                resources: 3000 + 3000 + 2 * 1000,
+                buildings: 500,
+                population: 4 * 50,
+                victory: 2000 + 4 * 200,
+            }
+        );
+        assert_eq!(score.total(), 8000 + 500 + 200 + 2800);
+    }
+
+    #[test]
+    fn lost_game_has_no_victory_bonus() {
+        let json = r#"{
+            "buildings": [{"name": "Hut"}],
+            "starting_resources": {"food": 1000},
+            "victory": [{"type": "population", "target": 100}]
+        }"#;
+        let ruleset = Ruleset::from_json(json).unwrap();
+        let mut game = Game::new(ruleset, 1);
+        game.apply(GameAction::Harvest).unwrap();
+        assert_eq!(Score::of(&game, 0).victory, 0);
+    }
+
+    #[test]
+    fn high_scores_are_sorted() {
+        let mut high_scores = HighScores::default();
+        assert_eq!(high_scores.add(entry("first", 100)), Some(0));
+        assert_eq!(high_scores.add(entry("second", 300)), Some(0));
+        assert_eq!(high_scores.add(entry("third", 100)), Some(2));
+        let names: Vec<_> = high_scores.entries.iter().map(|entry| entry.name.as_str()).collect();
+        assert_eq!(names, vec!["second", "first", "third"]);
+    }
+
+    #[test]
+    fn only_best_scores_are_kept() {
+        let mut high_scores = HighScores::default();
+        for score in 1..=HighScores::MAX_ENTRIES as u32 {
+            high_scores.add(entry("player", score * 10));
+        }
+        assert_eq!(high_scores.add(entry("worst", 5)), None);
+        assert_eq!(high_scores.add(entry("best", 500)), Some(0));
+        assert_eq!(high_scores.entries.len(), HighScores::MAX_ENTRIES);
+        assert_eq!(high_scores.entries.last().unwrap().score, 20);
+    }
+
+    #[test]
+    fn missing_file_is_empty_table() {
+        let high_scores = HighScores::load("this file does not exist.json").unwrap();
+        assert!(high_scores.entries.is_empty());
+    }
+}
@@ -0,0 +1,368 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::resources::Resources;
+use crate::ruleset::Ruleset;
+use crate::score::Score;
+use crate::strategy::{self, Scripted, Strategy, STRATEGIES};
+use crate::{Event, Game, GameAction, Outcome};
+
+pub const USAGE: &str = "Usage: simulate [<rules file>] [--games <count>] [--seed <number>] [--turns <count>] \
+[--strategy greedy|random]... [--script <building>,<building>,...]...";
+
+/// How many of the best build orders are reported.
+const BEST_BUILD_ORDERS: usize = 5;
+
+/// Strategy played in the simulated games.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StrategyKind {
+    /// One of [`STRATEGIES`](../strategy/constant.STRATEGIES.html).
+    Named(String),
+    /// Build order for a [`Scripted`](../strategy/struct.Scripted.html) strategy.
+    Script(Vec<String>),
+}
+
+impl StrategyKind {
+    pub fn create(&self, seed: u64) -> Box<dyn Strategy> {
+        match self {
+            StrategyKind::Named(name) => strategy::by_name(name, seed).expect("strategy names are checked when parsed"),
+            StrategyKind::Script(build_order) => Box::new(Scripted::new(build_order.clone())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Options {
+    pub rules_file: String,
+    pub games: u32,
+    pub seed: u64,
+    pub turns: u32,
+    pub strategies: Vec<StrategyKind>,
+}
+
+impl Options {
+    /// Parses command line arguments without the program name.
+    pub fn parse(args: &[String]) -> Result<Options, String> {
+        let mut rules_file = None;
+        let mut games = 1000;
+        let mut seed = 0;
+        let mut turns = 10;
+        let mut strategies = Vec::new();
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--games" => games = parse_number(args.next(), "game count")?,
+                "--seed" => seed = parse_number(args.next(), "seed")?,
+                "--turns" => turns = parse_number(args.next(), "turn count")?,
+                "--strategy" => match args.next() {
+                    Some(name) if STRATEGIES.contains(&name.as_str()) => {
+                        strategies.push(StrategyKind::Named(name.clone()))
+                    }
+                    Some(name) => return Err(format!("Unknown strategy '{}'", name)),
+                    None => return Err("Missing strategy".to_string()),
+                },
+                "--script" => match args.next() {
+                    Some(script) => {
+                        let build_order = script.split(',').map(|name| name.trim().to_string()).collect();
+                        strategies.push(StrategyKind::Script(build_order))
+                    }
+                    None => return Err("Missing build order".to_string()),
+                },
+                _ if rules_file.is_none() && !arg.starts_with("--") => rules_file = Some(arg.clone()),
+                _ => return Err(format!("Unexpected argument '{}'", arg)),
+            }
+        }
+
+        if games == 0 || turns == 0 {
+            return Err("At least one game of at least one turn is needed".to_string());
+        }
+        if strategies.is_empty() {
+            strategies = STRATEGIES.iter().map(|name| StrategyKind::Named(name.to_string())).collect();
+        }
+        Ok(Options {
+            rules_file: rules_file.unwrap_or_else(|| "rules.json".to_string()),
+            games,
+            seed,
+            turns,
+            strategies,
+        })
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(arg: Option<&String>, what: &str) -> Result<T, String> {
+    match arg.map(|number| number.parse()) {
+        Some(Ok(number)) => Ok(number),
+        Some(Err(_)) => Err(format!("Invalid {}", what)),
+        None => Err(format!("Missing {}", what)),
+    }
+}
+
+/// Result of one simulated game.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameRecord {
+    pub resources: Resources,
+    /// Buildings in the order in which they were built.
+    pub build_order: Vec<String>,
+    /// Turn, counted from 1, in which each building type was built for the first time.
+    pub first_built: HashMap<String, u32>,
+    pub won: bool,
+    pub score: u32,
+}
+
+/// Plays a single player game with the strategy until the game ends.
+/// Actions rejected by the rules end the turn instead.
+pub fn play(ruleset: &Ruleset, strategy: &mut dyn Strategy, turns: u32, seed: u64) -> GameRecord {
+    let mut game = Game::new(ruleset.clone(), turns).with_seed(seed);
+    let mut build_order = Vec::new();
+    let mut first_built = HashMap::new();
+    while !game.is_over() {
+        let turn = game.state().current_turn + 1;
+        let action = strategy.choose_action(&game);
+        let events = match game.apply(action) {
+            Ok(events) => events,
+            Err(_) => game.apply(GameAction::EndTurn).expect("turn can always be ended"),
+        };
+        for event in events {
+            if let Event::Built(name) = event {
+                first_built.entry(name.clone()).or_insert(turn);
+                build_order.push(name);
+            }
+        }
+    }
+    GameRecord {
+        resources: game.player(0).resources,
+        build_order,
+        first_built,
+        won: matches!(game.outcome(0), Some(Outcome::Won(_))),
+        score: Score::of(&game, 0).total(),
+    }
+}
+
+/// Plays the games with the strategy, game number `n` and its strategy get the seed `seed + n`.
+pub fn simulate(ruleset: &Ruleset, kind: &StrategyKind, games: u32, seed: u64, turns: u32) -> Report {
+    let records: Vec<_> = (0..games)
+        .map(|game| {
+            let seed = seed.wrapping_add(u64::from(game));
+            play(ruleset, kind.create(seed).as_mut(), turns, seed)
+        })
+        .collect();
+    Report::of(ruleset, &records)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BuildingStats {
+    pub name: String,
+    /// Games in which the building was built at least once.
+    pub games: u32,
+    /// Average turn of the first one, over the games in which it was built.
+    pub average_first_turn: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BuildOrderStats {
+    pub build_order: Vec<String>,
+    pub games: u32,
+    pub average_score: f64,
+}
+
+/// # Report
+/// Statistics over many simulated games.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    pub games: u32,
+    pub wins: u32,
+    pub average_score: f64,
+    /// Average gold, wood and food at the end of the game.
+    pub average_resources: (f64, f64, f64),
+    /// In the order of the ruleset.
+    pub buildings: Vec<BuildingStats>,
+    /// Best build orders by their average score.
+    pub build_orders: Vec<BuildOrderStats>,
+}
+
+impl Report {
+    pub fn of(ruleset: &Ruleset, records: &[GameRecord]) -> Report {
+        let games = records.len() as u32;
+        let average = |value: &dyn Fn(&GameRecord) -> u32| {
+            records.iter().map(|record| f64::from(value(record))).sum::<f64>() / f64::from(games.max(1))
+        };
+
+        let buildings = ruleset
+            .buildings
+            .iter()
+            .map(|building| {
+                let turns: Vec<_> = records
+                    .iter()
+                    .filter_map(|record| record.first_built.get(&building.name))
+                    .collect();
+                let sum: u32 = turns.iter().copied().sum();
+                BuildingStats {
+                    name: building.name.clone(),
+                    games: turns.len() as u32,
+                    average_first_turn: f64::from(sum) / turns.len().max(1) as f64,
+                }
+            })
+            .collect();
+
+        let mut scores: HashMap<&[String], Vec<u32>> = HashMap::new();
+        for record in records {
+            scores.entry(&record.build_order).or_default().push(record.score);
+        }
+        let mut build_orders: Vec<_> = scores
+            .into_iter()
+            .map(|(build_order, scores)| BuildOrderStats {
+                build_order: build_order.to_vec(),
+                games: scores.len() as u32,
+                average_score: scores.iter().map(|&score| f64::from(score)).sum::<f64>() / scores.len() as f64,
+            })
+            .collect();
+        // ties are broken by the build order so that the report does not depend on the hash order
+        build_orders.sort_by(|a, b| {
+            b.average_score
+                .partial_cmp(&a.average_score)
+                .unwrap()
+                .then_with(|| a.build_order.cmp(&b.build_order))
+        });
+        build_orders.truncate(BEST_BUILD_ORDERS);
+
+        Report {
+            games,
+            wins: records.iter().filter(|record| record.won).count() as u32,
+            average_score: average(&|record| record.score),
+            average_resources: (
+                average(&|record| record.resources.gold),
+                average(&|record| record.resources.wood),
+                average(&|record| record.resources.food),
+            ),
+            buildings,
+            build_orders,
+        }
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let percent = |games: u32| 100.0 * f64::from(games) / f64::from(self.games.max(1));
+        writeln!(f, "Games:         {}", self.games)?;
+        writeln!(f, "Won:           {} ({:.1} %)", self.wins, percent(self.wins))?;
+        writeln!(f, "Average score: {:.1}", self.average_score)?;
+        let (gold, wood, food) = self.average_resources;
+        writeln!(f, "Average resources: {:.1} gold, {:.1} wood, {:.1} food", gold, wood, food)?;
+        writeln!(f, "Buildings:")?;
+        for building in &self.buildings {
+            if building.games == 0 {
+                writeln!(f, "  {}: never built", building.name)?;
+            } else {
+                writeln!(
+                    f,
+                    "  {}: built in {:.1} % of games, first one in turn {:.1} on average",
+                    building.name,
+                    percent(building.games),
+                    building.average_first_turn
+                )?;
+            }
+        }
+        write!(f, "Best build orders:")?;
+        for (index, stats) in self.build_orders.iter().enumerate() {
+            let build_order = match stats.build_order.as_slice() {
+                [] => "nothing".to_string(),
+                build_order => build_order.join(", "),
+            };
+            write!(
+                f,
+                "\n  {}. {}: {:.1} points on average in {} games",
+                index + 1,
+                build_order,
+                stats.average_score,
+                stats.games
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ruleset::{ActionRules, MiningRules};
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    fn ruleset() -> Ruleset {
+        Ruleset::from_json(include_str!("../rules.json")).unwrap()
+    }
+
+    #[test]
+    fn parses_options() {
+        let options = Options::parse(&args(&["my.json", "--games", "50", "--script", "Farm, Sawmill"])).unwrap();
+        assert_eq!(options.rules_file, "my.json");
+        assert_eq!(options.games, 50);
+        assert_eq!(
+            options.strategies,
+            vec![StrategyKind::Script(vec!["Farm".to_string(), "Sawmill".to_string()])]
+        );
+
+        let options = Options::parse(&[]).unwrap();
+        assert_eq!((options.games, options.seed, options.turns), (1000, 0, 10));
+        assert_eq!(options.strategies.len(), STRATEGIES.len());
+    }
+
+    #[test]
+    fn rejects_invalid_options() {
+        assert!(Options::parse(&args(&["--games", "many"])).is_err());
+        assert!(Options::parse(&args(&["--games", "0"])).is_err());
+        assert!(Options::parse(&args(&["--strategy", "cheater"])).is_err());
+        assert!(Options::parse(&args(&["--seed"])).is_err());
+        assert!(Options::parse(&args(&["a.json", "b.json"])).is_err());
+    }
+
+    #[test]
+    fn scripted_game_records_first_farm() {
+        let mut ruleset = ruleset();
+        ruleset.mining = MiningRules::default();
+        ruleset.actions = ActionRules::default();
+        ruleset.events.clear();
+        let mut strategy = Scripted::new(vec!["Farm".to_string()]);
+        let record = play(&ruleset, &mut strategy, 10, 0);
+        assert_eq!(record.build_order, vec!["Farm".to_string()]);
+        // two turns of gold and one of wood, then the farm
+        assert_eq!(record.first_built.get("Farm"), Some(&4));
+    }
+
+    #[test]
+    fn report_averages_games() {
+        let records = vec![
+            GameRecord {
+                resources: Resources { gold: 100, wood: 0, food: 50 },
+                build_order: vec!["Farm".to_string()],
+                first_built: vec![("Farm".to_string(), 4)].into_iter().collect(),
+                won: true,
+                score: 1000,
+            },
+            GameRecord {
+                resources: Resources { gold: 300, wood: 0, food: 0 },
+                build_order: Vec::new(),
+                first_built: HashMap::new(),
+                won: false,
+                score: 500,
+            },
+        ];
+        let report = Report::of(&ruleset(), &records);
+        assert_eq!(report.wins, 1);
+        assert_eq!(report.average_resources, (200.0, 0.0, 25.0));
+        assert_eq!(report.buildings[0].games, 1);
+        assert_eq!(report.buildings[0].average_first_turn, 4.0);
+        assert_eq!(report.build_orders[0].build_order, vec!["Farm".to_string()]);
+        assert_eq!(report.build_orders[1].average_score, 500.0);
+    }
+
+    #[test]
+    fn same_seed_gives_same_report() {
+        let random = StrategyKind::Named("random".to_string());
+        let first = simulate(&ruleset(), &random, 20, 7, 10);
+        assert_eq!(first, simulate(&ruleset(), &random, 20, 7, 10));
+    }
+}
@@ -0,0 +1,457 @@
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+use crate::market::Commodity;
+use crate::resources::Resources;
+use crate::ruleset::{BuildingType, Ruleset, Technology, Upgrade};
+use crate::{Game, GameAction, PlayerState, ResourceType};
+
+/// Names of the strategies which can be created by [`by_name`](fn.by_name.html),
+/// [`Scripted`](struct.Scripted.html) needs a build order and is created directly.
+pub const STRATEGIES: [&str; 2] = ["greedy", "random"];
+
+/// # Strategy
+/// Computer player choosing the actions of the player on turn.
+pub trait Strategy {
+    fn name(&self) -> &str;
+
+    /// Chooses an action for the player on turn, it should be valid in the current state.
+    fn choose_action(&mut self, game: &Game) -> GameAction;
+}
+
+/// Creates the strategy with the given name, the seed is used by strategies which play randomly.
+pub fn by_name(name: &str, seed: u64) -> Option<Box<dyn Strategy>> {
+    match name {
+        "greedy" => Some(Box::new(Greedy)),
+        "random" => Some(Box::new(Random::new(seed))),
+        _ => None,
+    }
+}
+
+/// People should bring in the crops when the stored food runs low or when the fields are full.
+fn should_harvest(ruleset: &Ruleset, player: &PlayerState) -> bool {
+    let capacity = player.population.saturating_mul(ruleset.population.harvest_per_person);
+    let hungry = player.resources.food < food_needed(ruleset, player).saturating_mul(2);
+    player.crops > 0 && (hungry || player.crops >= capacity)
+}
+
+/// Food eaten by the people and the buildings in a turn.
+fn food_needed(ruleset: &Ruleset, player: &PlayerState) -> u32 {
+    let upkeep: u32 = player
+        .buildings
+        .iter()
+        .filter_map(|building| ruleset.building(&building.name))
+        .map(|building| building.upkeep.food)
+        .sum();
+    player.population.saturating_mul(ruleset.population.food_per_person).saturating_add(upkeep)
+}
+
+/// Mines the resource of which the most is missing to pay the cost, unless its mines are depleted.
+fn mine_for(player: &PlayerState, cost: &Resources) -> GameAction {
+    let missing_gold = cost.gold.saturating_sub(player.resources.gold);
+    let missing_wood = cost.wood.saturating_sub(player.resources.wood);
+    if (missing_wood > missing_gold && player.deposits.wood > 0) || player.deposits.gold == 0 {
+        GameAction::Mine(ResourceType::Wood)
+    } else {
+        GameAction::Mine(ResourceType::Gold)
+    }
+}
+
+/// Player on turn has enough action points left for the action.
+fn has_points_for(game: &Game, action: &GameAction) -> bool {
+    game.action_cost(action) <= game.player(game.current_player()).action_points
+}
+
+/// Ends the turn instead of an action for which the player has no action points left.
+fn or_end_turn(game: &Game, action: GameAction) -> GameAction {
+    if has_points_for(game, &action) {
+        action
+    } else {
+        GameAction::EndTurn
+    }
+}
+
+/// Buys gold or wood which is missing to pay the cost and cannot be mined anymore,
+/// with whatever is left of the other one after paying the cost.
+fn buy_missing(game: &Game, player: &PlayerState, cost: &Resources) -> Option<GameAction> {
+    for &(missing, other) in [(Commodity::Gold, Commodity::Wood), (Commodity::Wood, Commodity::Gold)].iter() {
+        let depleted = missing.amount(&player.deposits) == 0;
+        let surplus = other.amount(&player.resources).saturating_sub(other.amount(cost));
+        let worth_it = game.state().market.quote(other, surplus, missing) > 0;
+        if depleted && missing.amount(&player.resources) < missing.amount(cost) && worth_it {
+            return Some(GameAction::Trade(other, surplus, missing));
+        }
+    }
+    None
+}
+
+/// Builds on the first free site, when there is none the player has to explore first.
+fn build_or_explore(game: &Game, name: &str) -> GameAction {
+    match game.building_sites(game.current_player()).first() {
+        Some(&site) => GameAction::Build(name.to_string(), site),
+        None => GameAction::Explore,
+    }
+}
+
+/// Researches towards a technology which unlocks the building, starting with the missing prerequisites.
+/// Returns `None` while another research is in progress or when there is nothing to research.
+fn research_for(game: &Game, building: &str) -> Option<GameAction> {
+    let ruleset = game.ruleset();
+    let player = game.player(game.current_player());
+    if player.research.is_some() {
+        return None;
+    }
+    let available = game.available_technologies(game.current_player());
+    let mut needed: Vec<&str> = ruleset.unlocked_by(building).map(|technology| technology.name.as_str()).collect();
+    let mut index = 0;
+    while index < needed.len() {
+        let name = needed[index];
+        if available.iter().any(|technology| technology.name == name) {
+            return Some(GameAction::Research(name.to_string()));
+        }
+        for required in &ruleset.technology(name)?.requires {
+            if !player.researched.contains(required) && !needed.contains(&required.as_str()) {
+                needed.push(required);
+            }
+        }
+        index += 1;
+    }
+    None
+}
+
+/// # Greedy
+/// Keeps its people fed and builds whatever produces the most points as soon as it can afford it.
+/// When it cannot build anything, it researches upgrades of the buildings it already has,
+/// or what unlocks a building when all of them are locked,
+/// and buys what its mines cannot give anymore.
+pub struct Greedy;
+
+impl Greedy {
+    /// Points produced by the building in a turn, after its upkeep.
+    fn value(ruleset: &Ruleset, building: &BuildingType) -> i64 {
+        let weights = &ruleset.scoring.resource_weights;
+        let points = |amounts: &Resources| {
+            i64::from(amounts.gold) * i64::from(weights.gold)
+                + i64::from(amounts.wood) * i64::from(weights.wood)
+                + i64::from(amounts.food) * i64::from(weights.food)
+        };
+        points(&building.production) - points(&building.upkeep)
+    }
+}
+
+impl Strategy for Greedy {
+    fn name(&self) -> &str {
+        "greedy"
+    }
+
+    fn choose_action(&mut self, game: &Game) -> GameAction {
+        or_end_turn(game, Greedy::choose(game))
+    }
+}
+
+impl Greedy {
+    fn choose(game: &Game) -> GameAction {
+        let ruleset = game.ruleset();
+        let player = game.player(game.current_player());
+
+        if should_harvest(ruleset, player) {
+            return GameAction::Harvest;
+        }
+
+        let mut buildings: Vec<&BuildingType> = ruleset
+            .buildings
+            .iter()
+            .filter(|building| game.is_unlocked(game.current_player(), &building.name))
+            .collect();
+        // stable sort keeps the ruleset order for buildings of the same value
+        buildings.sort_by_key(|building| -Greedy::value(ruleset, building));
+        let can_build = ruleset.actions.costs.build <= player.action_points;
+        if let Some(building) = buildings.iter().find(|building| can_build && player.resources.can_afford(&building.cost)) {
+            return build_or_explore(game, &building.name);
+        }
+        if player.research.is_none() {
+            let upgrades_owned = |technology: &Technology| {
+                let owned = |upgrade: &Upgrade| player.buildings.iter().any(|built| built.name == upgrade.building);
+                technology.upgrades.iter().any(owned)
+            };
+            let technologies = game.available_technologies(game.current_player());
+            let upgrade = technologies
+                .iter()
+                .find(|technology| upgrades_owned(technology) && player.resources.can_afford(&technology.cost_per_turn));
+            if let Some(technology) = upgrade {
+                return GameAction::Research(technology.name.clone());
+            }
+        }
+
+        match buildings.first() {
+            Some(building) => buy_missing(game, player, &building.cost).unwrap_or_else(|| mine_for(player, &building.cost)),
+            // every building is locked, research towards the most valuable one
+            None => {
+                let mut locked: Vec<&BuildingType> = ruleset.buildings.iter().collect();
+                locked.sort_by_key(|building| -Greedy::value(ruleset, building));
+                locked
+                    .iter()
+                    .find_map(|building| research_for(game, &building.name))
+                    .unwrap_or_else(|| mine_for(player, &Resources::default()))
+            }
+        }
+    }
+}
+
+/// # Scripted
+/// Builds the buildings in the given order, researches what unlocks them and mines whatever
+/// the next one needs, so that designers can try out a build order.
+pub struct Scripted {
+    name: String,
+    build_order: Vec<String>,
+}
+
+impl Scripted {
+    pub fn new(build_order: Vec<String>) -> Scripted {
+        let name = format!("scripted [{}]", build_order.join(", "));
+        Scripted { name, build_order }
+    }
+}
+
+impl Strategy for Scripted {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn choose_action(&mut self, game: &Game) -> GameAction {
+        or_end_turn(game, self.choose(game))
+    }
+}
+
+impl Scripted {
+    fn choose(&self, game: &Game) -> GameAction {
+        let ruleset = game.ruleset();
+        let player = game.player(game.current_player());
+        if should_harvest(ruleset, player) {
+            return GameAction::Harvest;
+        }
+
+        // buildings which are already built were done by the previous steps of the script
+        let next = self
+            .build_order
+            .iter()
+            .enumerate()
+            .find(|(index, name)| {
+                let wanted = self.build_order[..=*index].iter().filter(|other| other == name).count();
+                player.buildings.iter().filter(|built| built.name == **name).count() < wanted
+            })
+            .and_then(|(_, name)| ruleset.building(name));
+        match next {
+            Some(building) if !game.is_unlocked(game.current_player(), &building.name) => {
+                research_for(game, &building.name).unwrap_or_else(|| mine_for(player, &building.cost))
+            }
+            Some(building) if player.resources.can_afford(&building.cost) => {
+                match build_or_explore(game, &building.name) {
+                    // the building has to wait for the next turn
+                    action @ GameAction::Build(..) if !has_points_for(game, &action) => mine_for(player, &building.cost),
+                    action => action,
+                }
+            }
+            Some(building) => mine_for(player, &building.cost),
+            None => mine_for(player, &Resources::default()),
+        }
+    }
+}
+
+/// # Random
+/// Picks any valid action, useful as a baseline for the other strategies.
+pub struct Random {
+    rng: StdRng,
+}
+
+impl Random {
+    pub fn new(seed: u64) -> Random {
+        Random {
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl Strategy for Random {
+    fn name(&self) -> &str {
+        "random"
+    }
+
+    fn choose_action(&mut self, game: &Game) -> GameAction {
+        let player = game.player(game.current_player());
+        let mut actions = vec![
+            GameAction::Harvest,
+            GameAction::Mine(ResourceType::Gold),
+            GameAction::Mine(ResourceType::Wood),
+            GameAction::Explore,
+        ];
+        let sites = game.building_sites(game.current_player());
+        let sold = *Commodity::ALL.choose(&mut self.rng).expect("there are commodities");
+        let others: Vec<Commodity> = Commodity::ALL.iter().copied().filter(|other| *other != sold).collect();
+        let bought = *others.choose(&mut self.rng).expect("there are other commodities");
+        let amount = sold.amount(&player.resources) / 2;
+        if game.state().market.quote(sold, amount, bought) > 0 {
+            actions.push(GameAction::Trade(sold, amount, bought));
+        }
+        if player.research.is_none() {
+            if let Some(technology) = game.available_technologies(game.current_player()).choose(&mut self.rng) {
+                actions.push(GameAction::Research(technology.name.clone()));
+            }
+        }
+        for building in &game.ruleset().buildings {
+            if player.resources.can_afford(&building.cost) && game.is_unlocked(game.current_player(), &building.name) {
+                if let Some(&site) = sites.choose(&mut self.rng) {
+                    actions.push(GameAction::Build(building.name.clone(), site));
+                }
+            }
+        }
+        actions.retain(|action| has_points_for(game, action));
+        actions.push(GameAction::EndTurn);
+        actions.choose(&mut self.rng).cloned().expect("the turn can always be ended")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::map::Building;
+    use crate::Research;
+
+    fn game() -> Game {
+        let ruleset = Ruleset::from_json(include_str!("../rules.json")).unwrap();
+        let mut players = Vec::new();
+        for name in STRATEGIES.iter() {
+            let mut player = PlayerState::new(name, &ruleset);
+            player.strategy = Some(name.to_string());
+            players.push(player);
+        }
+        Game::with_players(ruleset, players, 20)
+    }
+
+    fn play(game: &mut Game, seed: u64) {
+        let mut strategies: Vec<_> = STRATEGIES.iter().map(|name| by_name(name, seed).unwrap()).collect();
+        while !game.is_over() {
+            let action = strategies[game.current_player()].choose_action(game);
+            game.apply(action).unwrap();
+        }
+    }
+
+    #[test]
+    fn strategies_are_found_by_name() {
+        for name in STRATEGIES.iter() {
+            assert_eq!(by_name(name, 0).unwrap().name(), *name);
+        }
+        assert!(by_name("cheater", 0).is_none());
+    }
+
+    #[test]
+    fn greedy_builds_when_it_can_afford() {
+        let mut game = game();
+        game.state.players[0].resources = Resources { gold: 1000, wood: 500, food: 1000 };
+        let site = game.building_sites(0)[0];
+        assert_eq!(Greedy.choose_action(&game), GameAction::Build("Farm".to_string(), site));
+    }
+
+    #[test]
+    fn greedy_explores_without_building_sites() {
+        let mut game = game();
+        game.state.players[0].resources = Resources { gold: 1000, wood: 500, food: 1000 };
+        game.state.players[0].explored.clear();
+        assert_eq!(Greedy.choose_action(&game), GameAction::Explore);
+    }
+
+    #[test]
+    fn greedy_researches_upgrades_of_its_buildings() {
+        let mut game = game();
+        game.state.players[0].resources = Resources { gold: 500, wood: 0, food: 1000 };
+        let site = game.building_sites(0)[0];
+        game.state.players[0].buildings = vec![Building {
+            name: "Farm".to_string(),
+            position: site,
+        }];
+        assert_eq!(Greedy.choose_action(&game), GameAction::Research("Irrigation".to_string()));
+    }
+
+    #[test]
+    fn greedy_buys_what_cannot_be_mined() {
+        let mut game = game();
+        game.state.players[0].resources = Resources { gold: 1500, wood: 0, food: 1000 };
+        game.state.players[0].deposits.wood = 0;
+        assert_eq!(
+            Greedy.choose_action(&game),
+            GameAction::Trade(Commodity::Gold, 500, Commodity::Wood)
+        );
+    }
+
+    #[test]
+    fn greedy_mines_what_is_missing() {
+        let mut game = game();
+        game.state.players[0].resources = Resources { gold: 1000, wood: 0, food: 1000 };
+        assert_eq!(Greedy.choose_action(&game), GameAction::Mine(ResourceType::Wood));
+    }
+
+    #[test]
+    fn greedy_researches_when_everything_is_locked() {
+        let json = r#"{
+            "buildings": [{"name": "Hut", "cost": {"wood": 100}}],
+            "technologies": [{"name": "T", "turns": 1, "unlocks": ["Hut"]}]
+        }"#;
+        let ruleset = Ruleset::from_json(json).unwrap();
+        let players = vec![PlayerState::new("greedy", &ruleset)];
+        let mut game = Game::with_players(ruleset, players, 5);
+        assert_eq!(Greedy.choose_action(&game), GameAction::Research("T".to_string()));
+
+        game.state.players[0].research = Some(Research {
+            technology: "T".to_string(),
+            turns_done: 0,
+        });
+        assert_eq!(Greedy.choose_action(&game), GameAction::Mine(ResourceType::Gold));
+    }
+
+    #[test]
+    fn scripted_follows_build_order() {
+        let mut game = game();
+        let mut scripted = Scripted::new(vec!["Sawmill".to_string(), "Sawmill".to_string()]);
+        game.state.players[0].resources = Resources { gold: 400, wood: 200, food: 1000 };
+        let sawmill = |position| Building {
+            name: "Sawmill".to_string(),
+            position,
+        };
+        let sites = game.building_sites(0);
+        game.state.players[0].buildings = vec![sawmill(sites[0])];
+        assert_eq!(scripted.choose_action(&game), GameAction::Build("Sawmill".to_string(), sites[1]));
+
+        game.state.players[0].buildings.push(sawmill(sites[1]));
+        assert_eq!(scripted.choose_action(&game), GameAction::Mine(ResourceType::Gold));
+        assert_eq!(scripted.name(), "scripted [Sawmill, Sawmill]");
+    }
+
+    #[test]
+    fn scripted_researches_locked_buildings() {
+        let mut game = game();
+        let mut scripted = Scripted::new(vec!["Workshop".to_string()]);
+        game.state.players[0].resources = Resources { gold: 1000, wood: 1000, food: 1000 };
+        assert_eq!(scripted.choose_action(&game), GameAction::Research("Carpentry".to_string()));
+
+        game.state.players[0].researched.insert("Carpentry".to_string());
+        let site = game.building_sites(0)[0];
+        assert_eq!(scripted.choose_action(&game), GameAction::Build("Workshop".to_string(), site));
+    }
+
+    #[test]
+    fn strategies_only_choose_valid_actions() {
+        for seed in 0..5 {
+            play(&mut game(), seed);
+        }
+    }
+
+    #[test]
+    fn random_strategy_is_repeatable() {
+        let mut first = game();
+        let mut second = game();
+        play(&mut first, 42);
+        play(&mut second, 42);
+        assert_eq!(first.state(), second.state());
+    }
+}